@@ -0,0 +1,142 @@
+use iced::Theme;
+use iced::theme;
+
+use std::fmt;
+
+/// The theme of comet, either picked by the user or adopted from the
+/// palette of the connected application.
+#[derive(Debug, Clone)]
+pub struct Appearance {
+    choice: Choice,
+    /// The last choice other than the app palette, used until an
+    /// application provides its own.
+    fallback: Choice,
+    /// The latest palette of the connected application.
+    app: Option<Theme>,
+    follows_app: bool,
+    /// The light or dark preference of the system.
+    mode: theme::Mode,
+}
+
+/// An entry of the theme selector.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Choice {
+    App,
+    /// A light or dark theme, following the preference of the system.
+    System,
+    Builtin(Theme),
+}
+
+impl Choice {
+    pub fn all() -> Vec<Self> {
+        [Self::App, Self::System]
+            .into_iter()
+            .chain(Theme::ALL.iter().cloned().map(Self::Builtin))
+            .collect()
+    }
+
+    /// Parses a choice saved with its [`Display`](fmt::Display) name.
+    ///
+    /// The app palette is never saved, since it depends on the connected
+    /// application.
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name == "System" {
+            return Some(Self::System);
+        }
+
+        Theme::ALL
+            .iter()
+            .find(|theme| theme.to_string() == name)
+            .cloned()
+            .map(Self::Builtin)
+    }
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Choice::App => f.write_str("App palette"),
+            Choice::System => f.write_str("System"),
+            Choice::Builtin(theme) => theme.fmt(f),
+        }
+    }
+}
+
+impl Appearance {
+    pub fn new(fallback: Choice, follows_app: bool) -> Self {
+        let fallback = match fallback {
+            Choice::App => Choice::Builtin(Theme::CatppuccinMocha),
+            fallback => fallback,
+        };
+
+        Self {
+            choice: fallback.clone(),
+            fallback,
+            app: None,
+            follows_app,
+            mode: theme::Mode::None,
+        }
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.resolve(&self.choice)
+    }
+
+    fn resolve(&self, choice: &Choice) -> Theme {
+        match choice {
+            Choice::App => self
+                .app
+                .clone()
+                .unwrap_or_else(|| self.resolve(&self.fallback)),
+            Choice::System => match self.mode {
+                theme::Mode::Light => Theme::CatppuccinLatte,
+                theme::Mode::Dark | theme::Mode::None => Theme::CatppuccinMocha,
+            },
+            Choice::Builtin(theme) => theme.clone(),
+        }
+    }
+
+    pub fn choice(&self) -> &Choice {
+        &self.choice
+    }
+
+    pub fn fallback(&self) -> &Choice {
+        &self.fallback
+    }
+
+    pub fn follows_app(&self) -> bool {
+        self.follows_app
+    }
+
+    pub fn select(&mut self, choice: Choice) {
+        if choice != Choice::App {
+            self.fallback = choice.clone();
+        }
+
+        self.choice = choice;
+    }
+
+    /// Sets whether new palettes of the application replace the current
+    /// theme, switching to the latest one right away if so.
+    pub fn set_follows_app(&mut self, follows_app: bool) {
+        self.follows_app = follows_app;
+
+        if follows_app && self.app.is_some() {
+            self.choice = Choice::App;
+        }
+    }
+
+    /// Records a new palette of the application, which is only adopted
+    /// when following it.
+    pub fn adopt(&mut self, theme: Theme) {
+        self.app = Some(theme);
+
+        if self.follows_app {
+            self.choice = Choice::App;
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: theme::Mode) {
+        self.mode = mode;
+    }
+}
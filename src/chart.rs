@@ -1,27 +1,45 @@
 use crate::beacon;
 use crate::beacon::span::present;
 use crate::beacon::span::{self, Span};
+use crate::filter::Filter;
+use crate::session;
+use crate::stats::{self, Statistics};
 use crate::timeline::{self, Timeline};
+use crate::widget;
 
+use iced::keyboard;
 use iced::mouse;
-use iced::time::Duration;
-use iced::widget::canvas;
+use iced::time::{Duration, Instant, SystemTime};
+use iced::widget::{button, canvas, text, tooltip};
 use iced::window;
 use iced::{
     Bottom, Center, Color, Element, Event, Fill, Font, Pixels, Point, Rectangle, Renderer, Right,
-    Size, Theme, Top,
+    Size, Theme, Top, theme,
 };
 
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
 use std::fmt;
+use std::iter;
+
+mod cache;
+mod surface;
 
-pub use canvas::Cache;
+pub use cache::Cache;
+pub use surface::Surface;
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Debug, Clone)]
 pub enum Interaction {
     Hovered(timeline::Index),
     Selected(timeline::Index),
+    Pinned(timeline::Index),
     Unhovered,
     ZoomChanged(Zoom),
+    ExportRequested(Stage),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -38,6 +56,20 @@ pub enum Stage {
 }
 
 impl Stage {
+    /// Returns the [`timeline::Stage`] whose spans hold the durations of
+    /// this stage.
+    pub fn span(&self) -> timeline::Stage {
+        match self {
+            Stage::Update => timeline::Stage::Update,
+            Stage::View => timeline::Stage::View,
+            Stage::Layout => timeline::Stage::Layout,
+            Stage::Interact => timeline::Stage::Interact,
+            Stage::Draw => timeline::Stage::Draw,
+            Stage::Present | Stage::Prepare(_) | Stage::Render(_) => timeline::Stage::Present,
+            Stage::Custom(name) => timeline::Stage::Custom(name.clone()),
+        }
+    }
+
     pub fn duration(&self, event: &beacon::Event) -> Option<Duration> {
         let beacon::Event::SpanFinished { duration, span, .. } = event else {
             return None;
@@ -104,16 +136,32 @@ impl fmt::Display for Stage {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Zoom(u16);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "i16")]
+pub struct Zoom(i16);
 
 impl Zoom {
+    const MIN: i16 = -4;
+    const MAX: i16 = 10;
+
     pub fn increment(self) -> Self {
-        Self(self.0.saturating_add(1).min(10))
+        Self(self.0.saturating_add(1).min(Self::MAX))
     }
 
     pub fn decrement(self) -> Self {
-        Self(self.0.saturating_sub(1).max(1))
+        Self(self.0.saturating_sub(1).max(Self::MIN))
+    }
+
+    /// The width of a single bar, in pixels.
+    fn bar_width(self) -> f32 {
+        f32::from(self.0.max(1))
+    }
+
+    /// The amount of datapoints aggregated into a single bar.
+    ///
+    /// Zooming out past one pixel per bar doubles it on every step.
+    fn datapoints_per_bar(self) -> usize {
+        1 << (1 - self.0.min(1))
     }
 }
 
@@ -123,79 +171,458 @@ impl Default for Zoom {
     }
 }
 
+impl From<i16> for Zoom {
+    fn from(zoom: i16) -> Self {
+        // The settings may have been edited by hand
+        Self(zoom.clamp(Self::MIN, Self::MAX))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Settings {
+    pub zoom: Zoom,
+    pub mode: Mode,
+    pub axis: Axis,
+    pub budget: Option<Budget>,
+    pub anomalies: bool,
+    pub windows: Windows,
+    pub tail: Option<Tail>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    #[default]
+    Bars,
+    Histogram {
+        buckets: Buckets,
+    },
+}
+
+impl Mode {
+    pub fn toggle(self) -> Self {
+        match self {
+            Mode::Bars => Mode::Histogram {
+                buckets: Buckets::default(),
+            },
+            Mode::Histogram { .. } => Mode::Bars,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Axis {
+    /// One bar per datapoint, evenly spaced.
+    #[default]
+    Index,
+    /// Bars placed at the time they happened, leaving gaps for idle periods.
+    Time,
+}
+
+impl Axis {
+    pub fn toggle(self) -> Self {
+        match self {
+            Axis::Index => Axis::Time,
+            Axis::Time => Axis::Index,
+        }
+    }
+
+    fn pixels_per_second(self, zoom: Zoom) -> f32 {
+        zoom.bar_width() * 10.0 / zoom.datapoints_per_bar() as f32
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Buckets(u16);
+
+impl Buckets {
+    pub fn increment(self) -> Self {
+        Self(self.0.saturating_add(5).min(100))
+    }
+
+    pub fn decrement(self) -> Self {
+        Self(self.0.saturating_sub(5).max(5))
+    }
+}
+
+impl Default for Buckets {
+    fn default() -> Self {
+        Self(20)
+    }
+}
+
+/// The windows whose spans are charted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Windows {
+    #[default]
+    All,
+    Only(usize, window::Id),
+}
+
+impl Windows {
+    /// Whether the event belongs to the selected windows.
+    ///
+    /// Application-wide spans, like updates, belong to every window.
+    pub fn contains(self, event: &beacon::Event) -> bool {
+        let Windows::Only(_, id) = self else {
+            return true;
+        };
+
+        let beacon::Event::SpanFinished { span, .. } = event else {
+            return true;
+        };
+
+        match span {
+            Span::View { window }
+            | Span::Layout { window }
+            | Span::Interact { window }
+            | Span::Draw { window }
+            | Span::Present { window, .. } => *window == id,
+            Span::Boot | Span::Update { .. } | Span::Custom { .. } => true,
+        }
+    }
+}
+
+impl fmt::Display for Windows {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Windows::All => f.write_str("All windows"),
+            Windows::Only(number, _) => write!(f, "Window {number}"),
+        }
+    }
+}
+
+/// A frame budget drawn as a reference line on duration charts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget(Duration);
+
+impl Budget {
+    pub const FPS_60: Self = Self(Duration::from_micros(16_667));
+    pub const FPS_120: Self = Self(Duration::from_micros(8_333));
+
+    pub fn new(duration: Duration) -> Self {
+        Self(duration)
+    }
+
+    pub fn duration(self) -> Duration {
+        self.0
+    }
+
+    /// Cycles through no budget, 60 FPS and 120 FPS.
+    pub fn cycle(budget: Option<Self>) -> Option<Self> {
+        match budget {
+            None => Some(Self::FPS_60),
+            Some(Self::FPS_60) => Some(Self::FPS_120),
+            Some(_) => None,
+        }
+    }
+}
+
+/// A time window that bar charts are limited to, stretched across their
+/// whole width regardless of zoom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tail(Duration);
+
+impl Tail {
+    pub const SECONDS_10: Self = Self(Duration::from_secs(10));
+    pub const SECONDS_30: Self = Self(Duration::from_secs(30));
+    pub const SECONDS_60: Self = Self(Duration::from_secs(60));
+
+    pub fn new(duration: Duration) -> Self {
+        Self(duration)
+    }
+
+    pub fn duration(self) -> Duration {
+        self.0
+    }
+
+    /// Cycles through no tail, 10, 30 and 60 seconds.
+    pub fn cycle(tail: Option<Self>) -> Option<Self> {
+        match tail {
+            None => Some(Self::SECONDS_10),
+            Some(Self::SECONDS_10) => Some(Self::SECONDS_30),
+            Some(Self::SECONDS_30) => Some(Self::SECONDS_60),
+            Some(_) => None,
+        }
+    }
+}
+
+/// The statistics of a previously saved session, overlaid on the
+/// performance charts for comparison.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    stages: BTreeMap<String, Statistics>,
+}
+
+impl Baseline {
+    pub fn new(entries: impl IntoIterator<Item = session::Entry>) -> Self {
+        let mut collector = stats::Collector::new();
+
+        for event in entries.into_iter().filter_map(session::Entry::into_event) {
+            collector.push(&event);
+        }
+
+        Self {
+            stages: collector.statistics(),
+        }
+    }
+
+    fn get(&self, stage: &Stage) -> Option<Statistics> {
+        let name = match stage {
+            Stage::Custom(name) => name.clone(),
+            Stage::Prepare(_) | Stage::Render(_) => return None,
+            stage => stage.to_string(),
+        };
+
+        self.stages.get(&name).copied()
+    }
+
+    fn lines(statistics: Statistics) -> [(&'static str, Duration); 2] {
+        [("avg", statistics.mean), ("p95", statistics.p95)]
+    }
+}
+
+/// A [`widget::card`] for the performance chart of the given [`Stage`],
+/// with a button to export it as an image.
+pub fn card<'a>(
+    stage: Stage,
+    content: impl Into<Element<'a, Interaction>>,
+) -> Element<'a, Interaction> {
+    let title = stage.to_string();
+
+    widget::card_with_action(title, export(stage), content)
+}
+
+/// A small button to export the performance chart of the given [`Stage`].
+pub fn export<'a>(stage: Stage) -> Element<'a, Interaction> {
+    widget::tip(
+        button(text("Export").size(10))
+            .padding([0, 5])
+            .on_press(Interaction::ExportRequested(stage))
+            .style(button::text),
+        "Export as SVG",
+        tooltip::Position::Left,
+    )
+}
+
 pub fn performance<'a>(
     stage: Stage,
-    cache: &'a canvas::Cache,
+    cache: &'a Cache,
     timeline: &'a Timeline,
     offset: timeline::Playhead,
     selection: timeline::Playhead,
-    zoom: Zoom,
+    settings: Settings,
+    baseline: &Baseline,
 ) -> Element<'a, Interaction> {
-    match stage {
-        Stage::Update => updates(cache, timeline, offset, selection, zoom),
-        _ => canvas(BarChart {
-            datapoints: timeline
-                .timeframes(offset, move |event| stage.duration(event))
-                .map(|timeframe| (timeframe.index, timeframe.duration)),
-            to_float: |duration| duration.as_secs_f64(),
-            to_string: |duration| format!("{duration:?}"),
-            average: |duration, n| duration / n,
-            average_to_float: |duration| duration.as_secs_f64(),
-            average_to_string: |duration| format!("{duration:?}"),
+    let zoom = settings.zoom;
+    let baseline = baseline.get(&stage);
+
+    if let Mode::Histogram { buckets } = settings.mode {
+        return histogram(
+            stage,
             cache,
-            selection,
+            timeline,
+            offset,
+            settings.windows,
             zoom,
-        })
+            buckets,
+        );
+    }
+
+    match stage {
+        Stage::Update => updates(cache, timeline, offset, selection, settings, baseline),
+        _ => canvas(stage_chart(
+            stage, cache, timeline, offset, selection, settings, baseline,
+        ))
         .width(Fill)
         .height(Fill)
         .into(),
     }
 }
 
-pub fn updates<'a>(
-    cache: &'a canvas::Cache,
+/// What the charts of a screen are drawn from.
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+    pub timeline: &'a Timeline,
+    pub offset: timeline::Playhead,
+    pub selection: timeline::Playhead,
+    pub settings: Settings,
+    pub baseline: &'a Baseline,
+}
+
+/// Draws the performance chart of the given [`Stage`] on a [`Surface`] of
+/// the given size, like [`performance`] shows it in bar mode.
+pub fn render(
+    surface: &mut impl Surface,
+    size: Size,
+    stage: Stage,
+    context: Context<'_>,
+    palette: &theme::Palette,
+) {
+    let Context {
+        timeline,
+        offset,
+        selection,
+        settings,
+        baseline,
+    } = context;
+
+    let cache = Cache::new();
+    let baseline = baseline.get(&stage);
+
+    let _ = match stage {
+        Stage::Update => update_chart(&cache, timeline, offset, selection, settings, baseline)
+            .render(surface, size, 0, palette),
+        _ => stage_chart(
+            stage, &cache, timeline, offset, selection, settings, baseline,
+        )
+        .render(surface, size, 0, palette),
+    };
+}
+
+fn stage_chart<'a>(
+    stage: Stage,
+    cache: &'a Cache,
     timeline: &'a Timeline,
     offset: timeline::Playhead,
     selection: timeline::Playhead,
-    zoom: Zoom,
-) -> Element<'a, Interaction> {
-    canvas(BarChart {
+    settings: Settings,
+    baseline: Option<Statistics>,
+) -> BarChart<
+    'a,
+    impl Iterator<Item = (timeline::Index, SystemTime, Duration)> + Clone + 'a,
+    Duration,
+    Duration,
+> {
+    BarChart {
         datapoints: timeline
-            .updates(offset)
-            .map(|update| (update.index, update.duration)),
+            .timeframes(offset, stage.span(), move |event| {
+                stage
+                    .duration(event)
+                    .filter(|_| settings.windows.contains(event))
+            })
+            .map(|timeframe| (timeframe.index, timeframe.at, timeframe.duration)),
         to_float: |duration| duration.as_secs_f64(),
         to_string: |duration| format!("{duration:?}"),
         average: |duration, n| duration / n,
         average_to_float: |duration| duration.as_secs_f64(),
         average_to_string: |duration| format!("{duration:?}"),
+        from_float: Duration::from_secs_f64,
         cache,
         selection,
+        zoom: settings.zoom,
+        axis: settings.axis,
+        tail: settings.tail.map(Tail::duration),
+        gaps: timeline.gaps().collect(),
+        budget: settings.budget.map(Budget::duration),
+        anomalies: settings.anomalies,
+        baseline: baseline.map(Baseline::lines),
+        live_edge: offset.is_live(),
+    }
+}
+
+pub fn histogram<'a>(
+    stage: Stage,
+    cache: &'a Cache,
+    timeline: &'a Timeline,
+    offset: timeline::Playhead,
+    windows: Windows,
+    zoom: Zoom,
+    buckets: Buckets,
+) -> Element<'a, Interaction> {
+    canvas(Histogram {
+        durations: timeline
+            .timeframes(offset, stage.span(), move |event| {
+                stage.duration(event).filter(|_| windows.contains(event))
+            })
+            .map(|timeframe| timeframe.duration),
+        cache,
         zoom,
+        buckets,
     })
     .width(Fill)
     .height(Fill)
     .into()
 }
 
+pub fn updates<'a>(
+    cache: &'a Cache,
+    timeline: &'a Timeline,
+    offset: timeline::Playhead,
+    selection: timeline::Playhead,
+    settings: Settings,
+    baseline: Option<Statistics>,
+) -> Element<'a, Interaction> {
+    canvas(update_chart(
+        cache, timeline, offset, selection, settings, baseline,
+    ))
+    .width(Fill)
+    .height(Fill)
+    .into()
+}
+
+fn update_chart<'a>(
+    cache: &'a Cache,
+    timeline: &'a Timeline,
+    offset: timeline::Playhead,
+    selection: timeline::Playhead,
+    settings: Settings,
+    baseline: Option<Statistics>,
+) -> BarChart<
+    'a,
+    impl Iterator<Item = (timeline::Index, SystemTime, Duration)> + Clone + 'a,
+    Duration,
+    Duration,
+> {
+    BarChart {
+        datapoints: timeline
+            .updates(offset)
+            .map(|update| (update.index, update.at, update.duration)),
+        to_float: |duration| duration.as_secs_f64(),
+        to_string: |duration| format!("{duration:?}"),
+        average: |duration, n| duration / n,
+        average_to_float: |duration| duration.as_secs_f64(),
+        average_to_string: |duration| format!("{duration:?}"),
+        from_float: Duration::from_secs_f64,
+        cache,
+        selection,
+        zoom: settings.zoom,
+        axis: settings.axis,
+        tail: settings.tail.map(Tail::duration),
+        gaps: timeline.gaps().collect(),
+        budget: settings.budget.map(Budget::duration),
+        anomalies: settings.anomalies,
+        baseline: baseline.map(Baseline::lines),
+        live_edge: offset.is_live(),
+    }
+}
+
 pub fn tasks_spawned<'a>(
-    cache: &'a canvas::Cache,
+    cache: &'a Cache,
     timeline: &'a Timeline,
     offset: timeline::Playhead,
     selection: timeline::Playhead,
-    zoom: Zoom,
+    settings: Settings,
 ) -> Element<'a, Interaction> {
     canvas(BarChart {
         datapoints: timeline
             .updates(offset)
-            .map(|update| (update.index, update.tasks)),
+            .map(|update| (update.index, update.at, update.tasks)),
         cache,
         to_float: |amount| amount as f64,
         to_string: |amount| amount.to_string(),
         average: |amount, n| amount as f64 / n as f64,
         average_to_float: std::convert::identity,
         average_to_string: |average| format!("{:.1}", average),
+        from_float: std::convert::identity,
         selection,
-        zoom,
+        zoom: settings.zoom,
+        axis: settings.axis,
+        tail: settings.tail.map(Tail::duration),
+        gaps: timeline.gaps().collect(),
+        budget: None,
+        anomalies: settings.anomalies,
+        baseline: None,
+        live_edge: offset.is_live(),
     })
     .width(Fill)
     .height(Fill)
@@ -203,24 +630,32 @@ pub fn tasks_spawned<'a>(
 }
 
 pub fn subscriptions_alive<'a>(
-    cache: &'a canvas::Cache,
+    cache: &'a Cache,
     timeline: &'a Timeline,
     offset: timeline::Playhead,
     selection: timeline::Playhead,
-    zoom: Zoom,
+    settings: Settings,
 ) -> Element<'a, Interaction> {
     canvas(BarChart {
         datapoints: timeline
             .updates(offset)
-            .map(|update| (update.index, update.subscriptions)),
+            .map(|update| (update.index, update.at, update.subscriptions)),
         cache,
         to_float: |amount| amount as f64,
         to_string: |amount| amount.to_string(),
         average: |amount, n| amount as f64 / n as f64,
         average_to_float: std::convert::identity,
         average_to_string: |average| format!("{:.1}", average),
+        from_float: std::convert::identity,
         selection,
-        zoom,
+        zoom: settings.zoom,
+        axis: settings.axis,
+        tail: settings.tail.map(Tail::duration),
+        gaps: timeline.gaps().collect(),
+        budget: None,
+        anomalies: settings.anomalies,
+        baseline: None,
+        live_edge: offset.is_live(),
     })
     .width(Fill)
     .height(Fill)
@@ -228,32 +663,44 @@ pub fn subscriptions_alive<'a>(
 }
 
 pub fn layers_rendered<'a>(
-    cache: &'a canvas::Cache,
+    cache: &'a Cache,
     timeline: &'a Timeline,
     offset: timeline::Playhead,
     selection: timeline::Playhead,
-    zoom: Zoom,
+    settings: Settings,
 ) -> Element<'a, Interaction> {
     canvas(BarChart {
-        datapoints: timeline.seek_with_index(offset).filter_map(|(i, event)| {
-            if let beacon::Event::SpanFinished {
-                span: span::Span::Present { layers, .. },
-                ..
-            } = event
-            {
-                Some((i, *layers))
-            } else {
-                None
-            }
-        }),
+        datapoints: timeline.spans(offset, timeline::Stage::Present).filter_map(
+            move |(i, event)| {
+                if let beacon::Event::SpanFinished {
+                    span: span::Span::Present { layers, .. },
+                    at,
+                    ..
+                } = event
+                    && settings.windows.contains(event)
+                {
+                    Some((i, *at, *layers))
+                } else {
+                    None
+                }
+            },
+        ),
         cache,
         to_float: |amount| amount as f64,
         to_string: |amount| amount.to_string(),
         average: |amount, n| amount as f64 / n as f64,
         average_to_float: std::convert::identity,
         average_to_string: |average| format!("{:.1}", average),
+        from_float: std::convert::identity,
         selection,
-        zoom,
+        zoom: settings.zoom,
+        axis: settings.axis,
+        tail: settings.tail.map(Tail::duration),
+        gaps: timeline.gaps().collect(),
+        budget: None,
+        anomalies: settings.anomalies,
+        baseline: None,
+        live_edge: offset.is_live(),
     })
     .width(Fill)
     .height(Fill)
@@ -261,24 +708,117 @@ pub fn layers_rendered<'a>(
 }
 
 pub fn message_rate<'a>(
-    cache: &'a canvas::Cache,
+    cache: &'a Cache,
     timeline: &'a Timeline,
     offset: timeline::Playhead,
     selection: timeline::Playhead,
-    zoom: Zoom,
+    settings: Settings,
+    filter: Option<&Filter>,
 ) -> Element<'a, Interaction> {
-    let updates_per_second = timeline
-        .update_rate(offset)
-        .map(|update| (update.index, update.total));
+    let updates_per_second: Vec<_> = match filter {
+        Some(filter) if !filter.is_empty() => {
+            let mut buckets: Vec<(timeline::Index, SystemTime, usize)> = Vec::new();
+            let mut current = None;
+
+            for update in timeline
+                .updates(offset)
+                .filter(|update| filter.matches(&update.message))
+            {
+                let second = update
+                    .at
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+
+                match buckets.last_mut() {
+                    Some((index, _, total)) if current == Some(second) => {
+                        *index = update.index;
+                        *total += 1;
+                    }
+                    _ => {
+                        current = Some(second);
+                        buckets.push((update.index, update.at, 1));
+                    }
+                }
+            }
+
+            buckets
+        }
+        _ => timeline
+            .update_rate(offset)
+            .map(|update| (update.index, update.at, update.total))
+            .collect(),
+    };
 
     canvas(BarChart {
-        datapoints: updates_per_second,
+        datapoints: updates_per_second.into_iter(),
         cache,
         to_float: |amount| amount as f64,
         to_string: |amount| format!("{amount} msg/s"),
         average: |amount, n| amount as f64 / n as f64,
         average_to_float: std::convert::identity,
         average_to_string: |average| format!("{:.1} msg/s", average),
+        from_float: std::convert::identity,
+        selection,
+        zoom: settings.zoom,
+        axis: settings.axis,
+        tail: settings.tail.map(Tail::duration),
+        gaps: timeline.gaps().collect(),
+        budget: None,
+        anomalies: settings.anomalies,
+        baseline: None,
+        live_edge: false,
+    })
+    .width(Fill)
+    .height(Fill)
+    .into()
+}
+
+pub fn frame_rate<'a>(
+    cache: &'a Cache,
+    timeline: &'a Timeline,
+    offset: timeline::Playhead,
+    selection: timeline::Playhead,
+    settings: Settings,
+) -> Element<'a, Interaction> {
+    let frames_per_second = timeline
+        .frame_rate(offset)
+        .map(|frames| (frames.index, frames.at, frames.total));
+
+    canvas(BarChart {
+        datapoints: frames_per_second,
+        cache,
+        to_float: |amount| amount as f64,
+        to_string: |amount| format!("{amount} FPS"),
+        average: |amount, n| amount as f64 / n as f64,
+        average_to_float: std::convert::identity,
+        average_to_string: |average| format!("{:.1} FPS", average),
+        from_float: std::convert::identity,
+        selection,
+        zoom: settings.zoom,
+        axis: settings.axis,
+        tail: settings.tail.map(Tail::duration),
+        gaps: timeline.gaps().collect(),
+        budget: None,
+        anomalies: settings.anomalies,
+        baseline: None,
+        live_edge: false,
+    })
+    .width(Fill)
+    .height(Fill)
+    .into()
+}
+
+pub fn frames<'a>(
+    cache: &'a Cache,
+    timeline: &'a Timeline,
+    offset: timeline::Playhead,
+    selection: timeline::Playhead,
+    zoom: Zoom,
+) -> Element<'a, Interaction> {
+    canvas(Frames {
+        frames: timeline.frames(offset),
+        cache,
         selection,
         zoom,
     })
@@ -289,81 +829,915 @@ pub fn message_rate<'a>(
 
 struct BarChart<'a, I, T, A>
 where
-    I: Iterator<Item = (timeline::Index, T)>,
+    I: Iterator<Item = (timeline::Index, SystemTime, T)>,
 {
     datapoints: I,
-    cache: &'a canvas::Cache,
+    cache: &'a Cache,
     to_float: fn(T) -> f64,
     to_string: fn(T) -> String,
     average: fn(T, u32) -> A,
     average_to_float: fn(A) -> f64,
     average_to_string: fn(A) -> String,
+    from_float: fn(f64) -> A,
     selection: timeline::Playhead,
     zoom: Zoom,
+    axis: Axis,
+    tail: Option<Duration>,
+    gaps: Vec<timeline::Gap>,
+    budget: Option<T>,
+    anomalies: bool,
+    baseline: Option<[(&'static str, T); 2]>,
+    /// Whether new datapoints are drawn past an [`Edge`] of the cached
+    /// bars; only worth it when live, and never for rates, whose newest
+    /// bar keeps changing.
+    live_edge: bool,
 }
 
-impl<'a, I, T, A> canvas::Program<Interaction> for BarChart<'a, I, T, A>
+impl<I, T, A> BarChart<'_, I, T, A>
 where
-    I: Iterator<Item = (timeline::Index, T)> + Clone + 'a,
-    T: Ord + Copy + std::iter::Sum,
-    A: Copy,
+    I: Iterator<Item = (timeline::Index, SystemTime, T)> + Clone,
 {
-    type State = Option<timeline::Index>;
-
-    fn update(
+    /// Lays out the visible bars from right to left, returning the left
+    /// edge of each bar along with its datapoint.
+    fn layout(
         &self,
-        bar_hovered: &mut Option<timeline::Index>,
-        event: &Event,
-        bounds: Rectangle,
-        cursor: mouse::Cursor,
-    ) -> Option<canvas::Action<Interaction>> {
-        match event {
-            Event::Mouse(mouse::Event::CursorMoved { .. } | mouse::Event::ButtonPressed(_))
-            | Event::Window(window::Event::RedrawRequested(_)) => {
-                let Some(position) = cursor.position_in(bounds) else {
-                    if bar_hovered.is_some() {
-                        *bar_hovered = None;
+        width: f32,
+        pan: usize,
+    ) -> impl Iterator<Item = (f32, (timeline::Index, SystemTime, T))> + '_ {
+        let bar_width = self.zoom.bar_width();
+        let datapoints_per_bar = self.zoom.datapoints_per_bar();
+        let pixels_per_second = self.pixels_per_second(width);
+        let right = self.right(width, pan);
 
-                        return Some(canvas::Action::publish(Interaction::Unhovered));
-                    } else {
-                        return None;
+        let mut datapoints = self.datapoints.clone().skip(pan).peekable();
+        let newest = datapoints.peek().map(|(_, at, _)| *at);
+
+        datapoints
+            .enumerate()
+            .map(move |(i, datapoint)| {
+                let x = match pixels_per_second {
+                    None => right - bar_width * (i / datapoints_per_bar + 1) as f32,
+                    Some(pixels_per_second) => {
+                        let elapsed = newest
+                            .and_then(|newest| newest.duration_since(datapoint.1).ok())
+                            .unwrap_or_default();
+
+                        right - bar_width - elapsed.as_secs_f32() * pixels_per_second
                     }
                 };
 
-                let bar = ((bounds.width - position.x) / self.zoom.0 as f32) as usize;
+                (x, datapoint)
+            })
+            .take_while(move |(x, _)| {
+                if self.tail.is_some() {
+                    *x >= 0.0
+                } else {
+                    *x + bar_width > 0.0
+                }
+            })
+    }
+
+    /// Returns the right side of the newest bar.
+    ///
+    /// Past an [`Edge`], this is wherever the new datapoints have pushed it
+    /// since the cached bars were drawn, so those never move.
+    fn right(&self, width: f32, pan: usize) -> f32 {
+        let Some(edge) = self.edge(pan) else {
+            return width;
+        };
 
-                let (index, _datapoint) = self
+        match self.pixels_per_second(width) {
+            Some(pixels_per_second) => {
+                let elapsed = self
                     .datapoints
                     .clone()
-                    .nth(bar)
-                    .or_else(|| self.datapoints.clone().last())?;
+                    .next()
+                    .and_then(|(_, newest, _)| newest.duration_since(edge.at).ok())
+                    .unwrap_or_default();
 
-                if *bar_hovered != Some(index) {
-                    *bar_hovered = Some(index);
-                    self.cache.clear();
+                edge.x + elapsed.as_secs_f32() * pixels_per_second
+            }
+            None => edge.x + edge.bar_width * self.live(&edge).count() as f32,
+        }
+    }
 
-                    return Some(canvas::Action::publish(Interaction::Hovered(index)));
-                }
+    /// Leaves an [`Edge`] past the newest datapoint for the ones to come,
+    /// before the cached bars are drawn.
+    fn settle(&self, bounds: Size, pan: usize) {
+        const ROOM: f32 = 0.125;
 
-                let Event::Mouse(event) = event else {
-                    return None;
-                };
+        let bar_width = self.zoom.bar_width();
 
-                match event {
-                    mouse::Event::ButtonPressed(mouse::Button::Left) => {
-                        Some(canvas::Action::publish(Interaction::Selected(index)))
-                    }
-                    _ => None,
-                }
-            }
-            Event::Mouse(mouse::Event::WheelScrolled { delta }) if cursor.is_over(bounds) => {
-                match delta {
-                    mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => {
-                        let new_zoom = if y.is_sign_positive() {
-                            self.zoom.increment()
-                        } else {
-                            self.zoom.decrement()
-                        };
+        let edge = self
+            .datapoints
+            .clone()
+            .next()
+            .filter(|_| self.live_edge && pan == 0 && self.zoom.datapoints_per_bar() == 1)
+            .map(|(index, at, _)| Edge {
+                index,
+                at,
+                x: bounds.width - (bounds.width * ROOM).max(bar_width),
+                bar_width,
+                scale: Scale::default(),
+            });
+
+        self.cache.set_edge(edge);
+    }
+
+    /// Returns the [`Edge`] of the cached bars, if new datapoints can be
+    /// drawn past it.
+    fn edge(&self, pan: usize) -> Option<Edge> {
+        self.cache.edge().filter(|edge| {
+            self.live_edge
+                && pan == 0
+                && self.zoom.datapoints_per_bar() == 1
+                && edge.bar_width == self.zoom.bar_width()
+        })
+    }
+
+    /// Returns the datapoints past the given [`Edge`], newest first.
+    fn live(&self, edge: &Edge) -> impl Iterator<Item = (timeline::Index, SystemTime, T)> + '_ {
+        let index = edge.index;
+
+        self.datapoints
+            .clone()
+            .take_while(move |(datapoint, _, _)| *datapoint > index)
+    }
+
+    /// Returns whether the datapoints past the [`Edge`] no longer fit in the
+    /// room left for them, either at the right or at the top.
+    fn is_edge_full(&self, bounds: Size, pan: usize) -> bool {
+        let Some(edge) = self.edge(pan) else {
+            return false;
+        };
+
+        self.right(bounds.width, pan) > bounds.width
+            || self.live(&edge).any(|(_, _, datapoint)| {
+                (self.to_float)(datapoint) * edge.scale.pixels_per_unit > f64::from(bounds.height)
+            })
+    }
+
+    /// Returns the horizontal scale of the chart, if bars are placed at
+    /// the time they happened.
+    ///
+    /// A tail always places bars in time, fitting its whole window in the
+    /// chart.
+    fn pixels_per_second(&self, width: f32) -> Option<f32> {
+        match (self.tail, self.axis) {
+            (Some(tail), _) => Some(
+                (width - self.zoom.bar_width()).max(0.0) / tail.as_secs_f32().max(f32::EPSILON),
+            ),
+            (None, Axis::Time) => Some(self.axis.pixels_per_second(self.zoom)),
+            (None, Axis::Index) => None,
+        }
+    }
+
+    /// Draws the bars and their reference lines, without the hovered bar.
+    ///
+    /// This is shared by the canvas and [`render`], so exported charts look
+    /// exactly like the ones on screen.
+    ///
+    /// Returns the [`Scale`] of the bars, unless there are none.
+    fn render(
+        &self,
+        surface: &mut impl Surface,
+        bounds: Size,
+        pan: usize,
+        palette: &theme::Palette,
+    ) -> Option<Scale>
+    where
+        T: Ord + Copy + std::iter::Sum,
+        A: Copy,
+    {
+        let bar_width = self.zoom.bar_width();
+        let amount = self.layout(bounds.width, pan).count();
+
+        let datapoints = self.datapoints.clone().skip(pan);
+
+        let max = datapoints
+            .clone()
+            .take(amount)
+            .map(|(_, _, datapoint)| datapoint)
+            .max()?;
+
+        let average = {
+            let mut n = 0;
+
+            let sum = datapoints
+                .clone()
+                .take(amount * 3)
+                .map(|(_, _, datapoint)| {
+                    n += 1;
+                    datapoint
+                })
+                .sum::<T>();
+
+            (self.average)(sum, n)
+        };
+
+        let average_value = (self.average_to_float)(average);
+        let average_pixels = f64::from(bounds.height) / (2.0 * average_value);
+
+        let max_value = (self.to_float)(max);
+
+        // The baseline and the budget must stay visible, even when every
+        // bar is below them
+        let top_value = self
+            .baseline
+            .into_iter()
+            .flatten()
+            .map(|(_, value)| value)
+            .chain(self.budget)
+            .map(self.to_float)
+            .fold(max_value, f64::max);
+
+        let max_pixels = f64::from(bounds.height) / top_value;
+
+        // Everything sits at the bottom when there is nothing to scale
+        let pixels_per_unit = Some(average_pixels.min(max_pixels))
+            .filter(|pixels| pixels.is_finite())
+            .unwrap_or(0.0);
+
+        let scale = Scale {
+            pixels_per_unit,
+            average: average_value,
+        };
+
+        let mut sorted: Vec<T> = datapoints
+            .clone()
+            .take(amount)
+            .map(|(_, _, datapoint)| datapoint)
+            .collect();
+
+        sorted.sort_unstable();
+
+        let min = sorted[0];
+
+        let percentiles = [("p50", 0.50), ("p95", 0.95), ("p99", 0.99)]
+            .map(|(label, percentile)| (label, stats::percentile(&sorted, percentile)));
+
+        let (mean, deviation) = {
+            let values = sorted.iter().map(|datapoint| (self.to_float)(*datapoint));
+            let n = sorted.len() as f64;
+
+            let mean = values.clone().sum::<f64>() / n;
+            let variance = values.map(|value| (value - mean).powi(2)).sum::<f64>() / n;
+
+            (mean, variance.sqrt())
+        };
+
+        let deviation_top = bounds.height - ((mean + deviation) * pixels_per_unit) as f32;
+        let deviation_bottom =
+            bounds.height - ((mean - deviation).max(0.0) * pixels_per_unit) as f32;
+
+        if deviation > 0.0 {
+            surface.fill_rectangle(
+                Point::new(0.0, deviation_top.max(0.0)),
+                Size::new(bounds.width, deviation_bottom - deviation_top.max(0.0)),
+                palette.secondary.base.color.scale_alpha(0.1),
+            );
+        }
+
+        self.draw_gaps(surface, bounds, pan, palette);
+
+        let mut selected = false;
+
+        for column in self.columns(bounds.width, pan) {
+            self.draw_column(surface, &column, bounds.height, scale, palette);
+
+            if !selected
+                && let timeline::Playhead::Paused(selection) = self.selection
+                && selection >= column.index
+            {
+                surface.fill_rectangle(
+                    Point::new(column.x + bar_width, 0.0),
+                    Size::new(1.0, bounds.height),
+                    palette.background.base.text,
+                );
+
+                selected = true;
+            }
+        }
+
+        if self.anomalies {
+            let values: Vec<f64> = datapoints
+                .clone()
+                .take(amount + stats::BASELINE)
+                .map(|(_, _, datapoint)| (self.to_float)(datapoint))
+                .collect();
+
+            let anomalies = stats::anomalies(&values);
+
+            for ((x, (_, _, datapoint)), _) in self
+                .layout(bounds.width, pan)
+                .zip(anomalies)
+                .filter(|(_, is_anomaly)| *is_anomaly)
+            {
+                let top = (bounds.height - ((self.to_float)(datapoint) * pixels_per_unit) as f32)
+                    .max(7.0);
+                let center = x + bar_width / 2.0;
+
+                surface.fill_triangle(
+                    [
+                        Point::new(center - 3.0, top - 7.0),
+                        Point::new(center + 3.0, top - 7.0),
+                        Point::new(center, top - 1.0),
+                    ],
+                    palette.danger.strong.color,
+                );
+            }
+        }
+
+        const TICK: u32 = 5;
+
+        // A chart narrower than a bar has no room for ticks
+        if let Some(spacing) = self
+            .pixels_per_second(bounds.width)
+            .map(|pixels_per_second| pixels_per_second * TICK as f32)
+            .filter(|spacing| *spacing >= 1.0)
+        {
+            let right = self.right(bounds.width, pan);
+
+            for tick in 1..=(right / spacing) as u32 {
+                let x = right - bar_width - spacing * tick as f32;
+
+                surface.fill_rectangle(
+                    Point::new(x, bounds.height - 4.0),
+                    Size::new(1.0, 4.0),
+                    palette.background.base.text.scale_alpha(0.5),
+                );
+
+                surface.fill_text(canvas::Text {
+                    content: format!("-{}s", tick * TICK),
+                    position: Point::new(x, bounds.height - 6.0),
+                    color: palette.background.base.text.scale_alpha(0.5),
+                    size: Pixels(8.0),
+                    font: Font::MONOSPACE,
+                    align_x: Center.into(),
+                    align_y: Bottom,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        let average_y = bounds.height - (average_value * pixels_per_unit) as f32;
+        let max_y = bounds.height - (max_value * pixels_per_unit) as f32;
+        let min_y = bounds.height - ((self.to_float)(min) * pixels_per_unit) as f32;
+
+        if deviation > 0.0 {
+            surface.fill_text(canvas::Text {
+                content: format!(
+                    "±σ {}",
+                    (self.average_to_string)((self.from_float)(deviation))
+                ),
+                position: Point::new(5.0, deviation_bottom + 2.0),
+                color: palette.secondary.base.color,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_y: Top,
+                ..canvas::Text::default()
+            });
+        }
+
+        if min != max {
+            surface.fill_rectangle(
+                Point::new(0.0, min_y),
+                Size::new(bounds.width, 1.0),
+                palette.background.base.text.scale_alpha(0.15),
+            );
+
+            surface.fill_text(canvas::Text {
+                content: format!("min {}", (self.to_string)(min)),
+                position: Point::new(bounds.width - 5.0, min_y - 2.0),
+                color: palette.background.base.text,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_x: Right.into(),
+                align_y: Bottom,
+                ..canvas::Text::default()
+            });
+        }
+
+        for (label, percentile) in percentiles {
+            if percentile == max {
+                continue;
+            }
+
+            let y = bounds.height - ((self.to_float)(percentile) * pixels_per_unit) as f32;
+
+            surface.fill_rectangle(
+                Point::new(0.0, y),
+                Size::new(bounds.width, 1.0),
+                palette.primary.base.color.scale_alpha(0.3),
+            );
+
+            surface.fill_text(canvas::Text {
+                content: format!("{label} {}", (self.to_string)(percentile)),
+                position: Point::new(bounds.width - 5.0, y - 2.0),
+                color: palette.primary.base.color,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_x: Right.into(),
+                align_y: Bottom,
+                ..canvas::Text::default()
+            });
+        }
+
+        for (label, value) in self.baseline.into_iter().flatten() {
+            let y = bounds.height - ((self.to_float)(value) * pixels_per_unit) as f32;
+            let color = palette.background.strong.color;
+
+            surface.fill_rectangle(
+                Point::new(0.0, y),
+                Size::new(bounds.width, 1.0),
+                color.scale_alpha(0.6),
+            );
+
+            surface.fill_text(canvas::Text {
+                content: format!("baseline {label} {}", (self.to_string)(value)),
+                position: Point::new(bounds.width / 4.0, (y - 2.0).max(12.0)),
+                color,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_y: Bottom,
+                ..canvas::Text::default()
+            });
+        }
+
+        if let Some(budget) = self.budget {
+            let y = bounds.height - ((self.to_float)(budget) * pixels_per_unit) as f32;
+
+            surface.fill_rectangle(
+                Point::new(0.0, y),
+                Size::new(bounds.width, 1.0),
+                palette.danger.strong.color,
+            );
+
+            surface.fill_text(canvas::Text {
+                content: format!("budget {}", (self.to_string)(budget)),
+                position: Point::new(bounds.width / 2.0, (y - 2.0).max(12.0)),
+                color: palette.danger.strong.color,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_x: Center.into(),
+                align_y: Bottom,
+                ..canvas::Text::default()
+            });
+        }
+
+        surface.fill_rectangle(
+            Point::new(0.0, average_y),
+            Size::new(bounds.width, 1.0),
+            palette.background.base.text.scale_alpha(0.3),
+        );
+
+        surface.fill_text(canvas::Text {
+            content: format!("~{}", (self.average_to_string)(average)),
+            position: Point::new(5.0, average_y - 2.0),
+            color: palette.background.base.text,
+            size: Pixels(14.0),
+            font: Font::MONOSPACE,
+            align_y: Bottom,
+            ..canvas::Text::default()
+        });
+
+        surface.fill_rectangle(
+            Point::new(0.0, max_y),
+            Size::new(bounds.width, 1.0),
+            palette.background.base.text.scale_alpha(0.3),
+        );
+
+        surface.fill_text(canvas::Text {
+            content: (self.to_string)(max),
+            position: Point::new(bounds.width - 5.0, max_y + 2.0),
+            color: palette.background.base.text,
+            size: Pixels(10.0),
+            font: Font::MONOSPACE,
+            align_x: Right.into(),
+            ..canvas::Text::default()
+        });
+
+        if pan > 0 {
+            surface.fill_text(canvas::Text {
+                content: format!("{} bars back", pan),
+                position: Point::new(bounds.width / 2.0, 2.0),
+                color: palette.primary.base.color,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_x: Center.into(),
+                ..canvas::Text::default()
+            });
+        }
+
+        Some(scale)
+    }
+
+    /// Draws the bar of a [`Column`]; a candle with a tick at its average
+    /// when it aggregates many datapoints.
+    fn draw_column(
+        &self,
+        surface: &mut impl Surface,
+        column: &Column<T>,
+        height: f32,
+        scale: Scale,
+        palette: &theme::Palette,
+    ) where
+        T: Ord + Copy,
+    {
+        let bar_width = self.zoom.bar_width();
+        let average = column.total / column.count as f64;
+        let top = height - ((self.to_float)(column.max) * scale.pixels_per_unit) as f32;
+
+        let color = if self.budget.is_some_and(|budget| column.max > budget) {
+            palette.danger.base.color
+        } else if average < scale.average / 2.0 {
+            palette.success.strong.color
+        } else if average > scale.average * 3.0 {
+            palette.danger.weak.color
+        } else {
+            palette.background.strong.color
+        };
+
+        let bottom = if column.count == 1 {
+            height
+        } else {
+            (height - ((self.to_float)(column.min) * scale.pixels_per_unit) as f32).max(top + 1.0)
+        };
+
+        surface.fill_rectangle(
+            Point::new(column.x, top),
+            Size::new(bar_width, bottom - top),
+            color,
+        );
+
+        if column.count > 1 {
+            surface.fill_rectangle(
+                Point::new(column.x, height - (average * scale.pixels_per_unit) as f32),
+                Size::new(bar_width, 1.0),
+                palette.background.base.text,
+            );
+        }
+    }
+
+    /// Draws a striped region over the periods the application was
+    /// disconnected for, so data on each side isn't glued together.
+    ///
+    /// Without a time axis, the region is just wide enough to separate
+    /// the bars on each side of the disconnection.
+    fn draw_gaps(
+        &self,
+        surface: &mut impl Surface,
+        bounds: Size,
+        pan: usize,
+        palette: &theme::Palette,
+    ) where
+        T: Ord + Copy,
+    {
+        const STRIPE: f32 = 4.0;
+
+        let bar_width = self.zoom.bar_width();
+
+        let regions: Vec<(f32, f32)> = match self.pixels_per_second(bounds.width) {
+            Some(pixels_per_second) => {
+                let Some((_, newest, _)) = self.datapoints.clone().nth(pan) else {
+                    return;
+                };
+
+                let right = self.right(bounds.width, pan);
+
+                let x = |at: SystemTime| {
+                    let elapsed = newest.duration_since(at).unwrap_or_default();
+
+                    right - bar_width - elapsed.as_secs_f32() * pixels_per_second
+                };
+
+                self.gaps
+                    .iter()
+                    .filter_map(|gap| {
+                        let to = gap.to.filter(|_| gap.from < newest)?;
+
+                        Some((x(gap.from) + bar_width, x(to)))
+                    })
+                    .collect()
+            }
+            None => {
+                let columns = self.columns(bounds.width, pan);
+
+                self.gaps
+                    .iter()
+                    .filter_map(|gap| {
+                        let newer = columns
+                            .iter()
+                            .rposition(|column| column.index > gap.index)?;
+                        let older = columns.get(newer + 1)?;
+
+                        Some((older.x + bar_width, older.x + bar_width + STRIPE))
+                    })
+                    .collect()
+            }
+        };
+
+        for (left, right) in regions {
+            let left = left.max(0.0);
+            let right = right.min(bounds.width);
+
+            if right <= left {
+                continue;
+            }
+
+            surface.fill_rectangle(
+                Point::new(left, 0.0),
+                Size::new(right - left, bounds.height),
+                palette.danger.base.color.scale_alpha(0.05),
+            );
+
+            let mut x = left;
+
+            while x < right {
+                surface.fill_rectangle(
+                    Point::new(x, 0.0),
+                    Size::new(1.0_f32.min(right - x), bounds.height),
+                    palette.danger.base.color.scale_alpha(0.2),
+                );
+
+                x += STRIPE;
+            }
+        }
+    }
+
+    /// Aggregates the visible datapoints that share the same horizontal
+    /// position into a single [`Column`].
+    fn columns(&self, width: f32, pan: usize) -> Vec<Column<T>>
+    where
+        T: Ord + Copy,
+    {
+        self.aggregate(self.layout(width, pan))
+    }
+
+    /// Aggregates the given laid out datapoints into [`Column`]s.
+    fn aggregate(
+        &self,
+        layout: impl Iterator<Item = (f32, (timeline::Index, SystemTime, T))>,
+    ) -> Vec<Column<T>>
+    where
+        T: Ord + Copy,
+    {
+        let mut columns: Vec<Column<T>> = Vec::new();
+
+        for (x, (index, _at, datapoint)) in layout {
+            let value = (self.to_float)(datapoint);
+
+            match columns.last_mut() {
+                Some(column) if column.x.floor() == x.floor() => {
+                    column.min = column.min.min(datapoint);
+                    column.max = column.max.max(datapoint);
+                    column.total += value;
+                    column.count += 1;
+                }
+                _ => columns.push(Column {
+                    x,
+                    index,
+                    min: datapoint,
+                    max: datapoint,
+                    total: value,
+                    count: 1,
+                }),
+            }
+        }
+
+        columns
+    }
+
+    /// Returns the index of the bar at the given horizontal position,
+    /// clamping positions outside of the chart to its edges.
+    fn index_at(&self, x: f32, bounds: Rectangle, pan: usize) -> Option<timeline::Index> {
+        let x = x.clamp(0.0, bounds.width - 1.0);
+
+        let (_x, (index, _at, _datapoint)) = self
+            .layout(bounds.width, pan)
+            .find(|(left, _)| *left <= x)
+            .or_else(|| self.layout(bounds.width, pan).last())?;
+
+        Some(index)
+    }
+
+    /// Pans the chart by the given amount of bars, keeping at least one
+    /// bar in view.
+    fn pan(&self, pointer: &mut Pointer, bars: isize) -> Option<canvas::Action<Interaction>> {
+        let last = self.datapoints.clone().count().saturating_sub(1);
+        let pan = pointer.pan.saturating_add_signed(bars).min(last);
+
+        if pan == pointer.pan {
+            return None;
+        }
+
+        pointer.pan = pan;
+        self.cache.clear();
+
+        Some(canvas::Action::request_redraw().and_capture())
+    }
+}
+
+/// The datapoints drawn as a single bar of a [`BarChart`]; a candle when
+/// there is more than one of them.
+struct Column<T> {
+    x: f32,
+    index: timeline::Index,
+    min: T,
+    max: T,
+    total: f64,
+    count: usize,
+}
+
+/// How the values of a [`BarChart`] map to its height.
+#[derive(Debug, Clone, Copy, Default)]
+struct Scale {
+    pixels_per_unit: f64,
+    average: f64,
+}
+
+/// The newest bar in the cached geometry of a [`BarChart`], past which new
+/// datapoints are drawn on a live layer until they run out of room.
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    index: timeline::Index,
+    at: SystemTime,
+    /// The right side of the newest cached bar.
+    x: f32,
+    bar_width: f32,
+    scale: Scale,
+}
+
+/// How often a [`BarChart`] publishes [`Interaction::Hovered`] while the
+/// playhead is being scrubbed.
+const SCRUB_INTERVAL: Duration = Duration::from_millis(30);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Pointer {
+    hovered: Option<timeline::Index>,
+    scrubbed_at: Option<Instant>,
+    /// The amount of bars the chart is panned back from the playhead.
+    pan: usize,
+    panning: Option<Panning>,
+    modifiers: keyboard::Modifiers,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Panning {
+    x: f32,
+    pan: usize,
+}
+
+impl<'a, I, T, A> canvas::Program<Interaction> for BarChart<'a, I, T, A>
+where
+    I: Iterator<Item = (timeline::Index, SystemTime, T)> + Clone + 'a,
+    T: Ord + Copy + std::iter::Sum,
+    A: Copy,
+{
+    type State = Pointer;
+
+    fn update(
+        &self,
+        state: &mut Pointer,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Interaction>> {
+        match event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = *modifiers;
+
+                None
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) => {
+                let position = cursor.position_in(bounds)?;
+
+                state.panning = Some(Panning {
+                    x: position.x,
+                    pan: state.pan,
+                });
+
+                Some(canvas::Action::capture())
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Middle))
+                if state.panning.is_some() =>
+            {
+                state.panning = None;
+
+                Some(canvas::Action::capture())
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) if state.panning.is_some() => {
+                let panning = state.panning?;
+                let bars = ((position.x - bounds.x - panning.x) / self.zoom.bar_width()) as isize
+                    * self.zoom.datapoints_per_bar() as isize;
+
+                self.pan(state, panning.pan as isize + bars - state.pan as isize)
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta })
+                if state.modifiers.shift() && cursor.is_over(bounds) =>
+            {
+                // Some platforms turn shift + wheel into horizontal scrolling
+                let bars = match *delta {
+                    mouse::ScrollDelta::Lines { x, y } => {
+                        let lines = if x != 0.0 { x } else { y };
+
+                        (lines * 10.0) as isize
+                    }
+                    mouse::ScrollDelta::Pixels { x, y } => {
+                        let pixels = if x != 0.0 { x } else { y };
+
+                        (pixels / self.zoom.bar_width()) as isize
+                            * self.zoom.datapoints_per_bar() as isize
+                    }
+                };
+
+                self.pan(state, bars)
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) if state.scrubbed_at.is_some() => {
+                let index = self.index_at(position.x - bounds.x, bounds, state.pan)?;
+
+                if state.hovered == Some(index)
+                    || state
+                        .scrubbed_at
+                        .is_some_and(|scrubbed_at| scrubbed_at.elapsed() < SCRUB_INTERVAL)
+                {
+                    return None;
+                }
+
+                state.hovered = Some(index);
+                state.scrubbed_at = Some(Instant::now());
+
+                Some(canvas::Action::publish(Interaction::Hovered(index)).and_capture())
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                if state.scrubbed_at.is_some() =>
+            {
+                state.scrubbed_at = None;
+
+                let index = cursor
+                    .position()
+                    .and_then(|position| self.index_at(position.x - bounds.x, bounds, state.pan))
+                    .or(state.hovered)?;
+
+                state.hovered = Some(index);
+                self.cache.clear();
+
+                Some(canvas::Action::publish(Interaction::Pinned(index)).and_capture())
+            }
+            Event::Window(window::Event::RedrawRequested(_))
+                if self.is_edge_full(bounds.size(), state.pan) =>
+            {
+                self.cache.clear();
+
+                self.cache
+                    .redraw_at()
+                    .map(canvas::Action::request_redraw_at)
+            }
+            Event::Window(window::Event::RedrawRequested(_))
+                if self.cache.redraw_at().is_some() =>
+            {
+                self.cache
+                    .redraw_at()
+                    .map(canvas::Action::request_redraw_at)
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. } | mouse::Event::ButtonPressed(_))
+            | Event::Window(window::Event::RedrawRequested(_)) => {
+                let Some(position) = cursor.position_in(bounds) else {
+                    if state.hovered.is_some() && state.scrubbed_at.is_none() {
+                        state.hovered = None;
+
+                        return Some(canvas::Action::publish(Interaction::Unhovered));
+                    } else {
+                        return None;
+                    }
+                };
+
+                let index = self.index_at(position.x, bounds, state.pan)?;
+
+                if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+                    state.hovered = Some(index);
+                    state.scrubbed_at = Some(Instant::now());
+                    self.cache.clear();
+
+                    return Some(canvas::Action::publish(Interaction::Pinned(index)).and_capture());
+                }
+
+                if state.hovered == Some(index) {
+                    return None;
+                }
+
+                state.hovered = Some(index);
+
+                Some(canvas::Action::publish(Interaction::Hovered(index)))
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) if cursor.is_over(bounds) => {
+                match delta {
+                    mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => {
+                        let new_zoom = if y.is_sign_positive() {
+                            self.zoom.increment()
+                        } else {
+                            self.zoom.decrement()
+                        };
 
                         if new_zoom == self.zoom {
                             return None;
@@ -379,160 +1753,468 @@ where
 
     fn draw(
         &self,
-        _state: &Self::State,
+        state: &Pointer,
         renderer: &Renderer,
         theme: &Theme,
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
         let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
-            let cursor = cursor.position_in(bounds);
-
             let bounds = frame.size();
+
+            self.settle(bounds, state.pan);
+
+            let scale = self.render(frame, bounds, state.pan, theme.palette());
+
+            self.cache.set_edge(
+                self.cache
+                    .edge()
+                    .zip(scale)
+                    .map(|(edge, scale)| Edge { scale, ..edge }),
+            );
+        });
+
+        // Datapoints past the edge of the cached bars get a layer of their
+        // own, so the cached bars are only redrawn once it fills up
+        let live = self
+            .edge(state.pan)
+            .filter(|edge| self.live(edge).next().is_some())
+            .map(|edge| {
+                let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+                let columns = self.aggregate(
+                    self.layout(bounds.width, state.pan)
+                        .take_while(|(_, (index, _, _))| *index > edge.index),
+                );
+
+                for column in columns {
+                    self.draw_column(
+                        &mut frame,
+                        &column,
+                        bounds.height,
+                        edge.scale,
+                        theme.palette(),
+                    );
+                }
+
+                frame.into_geometry()
+            });
+
+        // The hovered bar is drawn on top of the cached geometry, so
+        // moving the cursor around never redraws every bar
+        let overlay = cursor.position_in(bounds).and_then(|cursor| {
+            let bar_width = self.zoom.bar_width();
+
+            let column = self
+                .columns(bounds.width, state.pan)
+                .into_iter()
+                .find(|column| (column.x..column.x + bar_width).contains(&cursor.x))?;
+
             let palette = theme.palette();
+            let mut frame = canvas::Frame::new(renderer, bounds.size());
 
-            let bar_width = f32::from(self.zoom.0);
-            let amount = (bounds.width / bar_width).ceil() as usize;
+            frame.fill_rectangle(
+                Point::new(column.x, 0.0),
+                Size::new(bar_width, bounds.height),
+                Color::BLACK.scale_alpha(0.3),
+            );
 
-            let datapoints = self.datapoints.clone();
+            let average = column.total / column.count as f64;
+            let fits = cursor.y >= 10.0;
 
-            let Some(max) = datapoints
-                .clone()
-                .take(amount)
-                .map(|(_, datapoint)| datapoint)
-                .max()
-            else {
-                return;
-            };
+            frame.fill_text(canvas::Text {
+                content: if column.count == 1 {
+                    (self.to_string)(column.max)
+                } else {
+                    format!(
+                        "{}..{} (~{})",
+                        (self.to_string)(column.min),
+                        (self.to_string)(column.max),
+                        (self.average_to_string)((self.from_float)(average)),
+                    )
+                },
+                position: cursor,
+                color: palette.background.base.text,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_x: Center.into(),
+                align_y: if fits { Bottom } else { Top },
+                ..canvas::Text::default()
+            });
 
-            let average = {
-                let mut n = 0;
+            Some(frame.into_geometry())
+        });
 
-                let sum = datapoints
-                    .clone()
-                    .take(amount * 3)
-                    .map(|(_, datapoint)| {
-                        n += 1;
-                        datapoint
-                    })
-                    .sum::<T>();
+        iter::once(geometry).chain(live).chain(overlay).collect()
+    }
+}
 
-                (self.average)(sum, n)
+/// The distribution of the durations that would fit in a [`BarChart`] of
+/// the same size and zoom.
+struct Histogram<'a, I>
+where
+    I: Iterator<Item = Duration>,
+{
+    durations: I,
+    cache: &'a Cache,
+    zoom: Zoom,
+    buckets: Buckets,
+}
+
+impl<I> Histogram<'_, I>
+where
+    I: Iterator<Item = Duration> + Clone,
+{
+    const LABELS_HEIGHT: f32 = 14.0;
+
+    fn distribution(&self, bounds: Size) -> Option<(Duration, Duration, Vec<usize>)> {
+        let amount =
+            (bounds.width / self.zoom.bar_width()).ceil() as usize * self.zoom.datapoints_per_bar();
+        let durations: Vec<_> = self.durations.clone().take(amount).collect();
+
+        let min = durations.iter().min().copied()?;
+        let max = durations.iter().max().copied()?;
+
+        let buckets = usize::from(self.buckets.0);
+        let range = (max - min).as_secs_f64();
+        let mut counts = vec![0; buckets];
+
+        for duration in durations {
+            let bucket = if range > 0.0 {
+                ((duration - min).as_secs_f64() / range * buckets as f64) as usize
+            } else {
+                0
             };
 
-            let average_value = (self.average_to_float)(average);
-            let average_pixels = f64::from(bounds.height) / (2.0 * average_value);
+            counts[bucket.min(buckets - 1)] += 1;
+        }
+
+        Some((min, max, counts))
+    }
+}
+
+impl<'a, I> canvas::Program<Interaction> for Histogram<'a, I>
+where
+    I: Iterator<Item = Duration> + Clone + 'a,
+{
+    type State = ();
 
-            let max_value = (self.to_float)(max);
-            let max_pixels = f64::from(bounds.height) / max_value;
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Interaction>> {
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) if cursor.is_over(bounds) => {
+                Some(canvas::Action::request_redraw())
+            }
+            Event::Window(window::Event::RedrawRequested(_)) => self
+                .cache
+                .redraw_at()
+                .map(canvas::Action::request_redraw_at),
+            _ => None,
+        }
+    }
 
-            let pixels_per_unit = average_pixels.min(max_pixels);
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let palette = theme.palette();
 
-            let mut selected = false;
+        let Some((min, max, counts)) = self.distribution(bounds.size()) else {
+            return Vec::new();
+        };
 
-            for (i, (index, datapoint)) in datapoints.take(amount).enumerate() {
-                let value = (self.to_float)(datapoint);
-                let bar_height = (value * pixels_per_unit) as f32;
+        let peak = counts.iter().max().copied().unwrap_or(1).max(1);
+        let bar_width = bounds.width / counts.len() as f32;
+        let height = bounds.height - Self::LABELS_HEIGHT;
 
-                let bar = Rectangle {
-                    x: bounds.width - bar_width * (i + 1) as f32,
-                    y: bounds.height - bar_height,
-                    width: bar_width,
-                    height: bar_height,
-                };
+        let bars = self.cache.draw(renderer, bounds.size(), |frame| {
+            for (i, count) in counts.iter().enumerate() {
+                let bar_height = height * *count as f32 / peak as f32;
 
                 frame.fill_rectangle(
-                    bar.position(),
-                    bar.size(),
-                    if value < average_value / 2.0 {
-                        palette.success.strong.color
-                    } else if value > average_value * 3.0 {
-                        palette.danger.weak.color
-                    } else {
-                        palette.background.strong.color
-                    },
+                    Point::new(i as f32 * bar_width, height - bar_height),
+                    Size::new((bar_width - 1.0).max(1.0), bar_height),
+                    palette.background.strong.color,
                 );
+            }
+
+            frame.fill_text(canvas::Text {
+                content: format!("{min:?}"),
+                position: Point::new(0.0, bounds.height),
+                color: palette.background.base.text,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_y: Bottom,
+                ..canvas::Text::default()
+            });
+
+            frame.fill_text(canvas::Text {
+                content: format!("{max:?}"),
+                position: Point::new(frame.width(), bounds.height),
+                color: palette.background.base.text,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_x: Right.into(),
+                align_y: Bottom,
+                ..canvas::Text::default()
+            });
+        });
+
+        let mut overlay = canvas::Frame::new(renderer, bounds.size());
+
+        if let Some(position) = cursor.position_in(bounds) {
+            let bucket = ((position.x / bar_width) as usize).min(counts.len() - 1);
+            let step = (max - min) / counts.len() as u32;
+
+            overlay.fill_rectangle(
+                Point::new(bucket as f32 * bar_width, 0.0),
+                Size::new(bar_width, height),
+                Color::BLACK.scale_alpha(0.3),
+            );
+
+            let fits = position.y >= 10.0;
+
+            overlay.fill_text(canvas::Text {
+                content: format!(
+                    "{:?}..{:?}: {}",
+                    min + step * bucket as u32,
+                    min + step * (bucket as u32 + 1),
+                    counts[bucket]
+                ),
+                position,
+                color: palette.background.base.text,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_x: Center.into(),
+                align_y: if fits { Bottom } else { Top },
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![bars, overlay.into_geometry()]
+    }
+}
+
+/// A stacked bar per frame, splitting its total time in stages.
+struct Frames<'a, I>
+where
+    I: Iterator<Item = timeline::Frame>,
+{
+    frames: I,
+    cache: &'a Cache,
+    selection: timeline::Playhead,
+    zoom: Zoom,
+}
+
+impl<I> Frames<'_, I>
+where
+    I: Iterator<Item = timeline::Frame> + Clone,
+{
+    const STAGES: [Stage; 6] = [
+        Stage::Update,
+        Stage::View,
+        Stage::Layout,
+        Stage::Interact,
+        Stage::Draw,
+        Stage::Present,
+    ];
+
+    fn frame_at(&self, x: f32, bounds: Rectangle) -> Option<timeline::Frame> {
+        let bar =
+            ((bounds.width - x.clamp(0.0, bounds.width - 1.0)) / self.zoom.bar_width()) as usize;
+
+        self.frames.clone().nth(bar)
+    }
+
+    fn color(stage: usize, palette: &theme::Palette) -> Color {
+        match stage {
+            0 => palette.primary.base.color,
+            1 => palette.success.base.color,
+            2 => palette.success.strong.color,
+            3 => palette.secondary.base.color,
+            4 => palette.danger.weak.color,
+            _ => palette.primary.weak.color,
+        }
+    }
+}
+
+impl<'a, I> canvas::Program<Interaction> for Frames<'a, I>
+where
+    I: Iterator<Item = timeline::Frame> + Clone + 'a,
+{
+    type State = Option<timeline::Index>;
+
+    fn update(
+        &self,
+        hovered: &mut Option<timeline::Index>,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Interaction>> {
+        if let Event::Window(window::Event::RedrawRequested(_)) = event {
+            return self
+                .cache
+                .redraw_at()
+                .map(canvas::Action::request_redraw_at);
+        }
+
+        let Event::Mouse(event) = event else {
+            return None;
+        };
+
+        let Some(position) = cursor.position_in(bounds) else {
+            if hovered.take().is_some() {
+                return Some(canvas::Action::publish(Interaction::Unhovered));
+            }
+
+            return None;
+        };
+
+        let frame = self.frame_at(position.x, bounds)?;
+
+        match event {
+            mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                Some(canvas::Action::publish(Interaction::Pinned(frame.index)).and_capture())
+            }
+            mouse::Event::CursorMoved { .. } if *hovered != Some(frame.index) => {
+                *hovered = Some(frame.index);
+
+                Some(canvas::Action::publish(Interaction::Hovered(frame.index)))
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        hovered: &Option<timeline::Index>,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let palette = theme.palette();
+
+        let bar_width = self.zoom.bar_width();
+        let amount = (bounds.width / bar_width).ceil() as usize;
+
+        let bars = self.cache.draw(renderer, bounds.size(), |frame| {
+            let Some(max) = self
+                .frames
+                .clone()
+                .take(amount)
+                .map(|frame| frame.total())
+                .max()
+                .filter(|max| !max.is_zero())
+            else {
+                return;
+            };
+
+            let pixels_per_second = f64::from(frame.height()) / max.as_secs_f64();
+            let mut selected = false;
+
+            for (i, stages) in self.frames.clone().take(amount).enumerate() {
+                let x = frame.width() - bar_width * (i + 1) as f32;
+                let mut y = frame.height();
+
+                for (stage, duration) in stages.stages().into_iter().enumerate() {
+                    let height = (duration.as_secs_f64() * pixels_per_second) as f32;
+                    y -= height;
+
+                    frame.fill_rectangle(
+                        Point::new(x, y),
+                        Size::new(bar_width, height),
+                        Self::color(stage, palette),
+                    );
+                }
 
                 if !selected
                     && let timeline::Playhead::Paused(selection) = self.selection
-                    && selection >= index
+                    && selection >= stages.index
                 {
                     frame.fill_rectangle(
-                        Point::new(bar.x + bar.width, 0.0),
-                        Size::new(1.0, bounds.height),
+                        Point::new(x + bar_width, 0.0),
+                        Size::new(1.0, frame.height()),
                         palette.background.base.text,
                     );
 
                     selected = true;
                 }
-
-                let bar_overlay = Rectangle {
-                    y: 0.0,
-                    height: bounds.height,
-                    ..bar
-                };
-
-                match cursor {
-                    Some(cursor) if bar_overlay.contains(cursor) => {
-                        frame.fill_rectangle(
-                            bar_overlay.position(),
-                            bar_overlay.size(),
-                            Color::BLACK.scale_alpha(0.3),
-                        );
-
-                        let fits = cursor.y >= 10.0;
-
-                        frame.fill_text(canvas::Text {
-                            content: (self.to_string)(datapoint),
-                            position: cursor,
-                            color: palette.background.base.text,
-                            size: Pixels(10.0),
-                            font: Font::MONOSPACE,
-                            align_x: Center.into(),
-                            align_y: if fits { Bottom } else { Top },
-                            ..canvas::Text::default()
-                        });
-                    }
-                    _ => {}
-                }
             }
 
-            let average_y = bounds.height - (average_value * pixels_per_unit) as f32;
-            let max_y = bounds.height - (max_value * pixels_per_unit) as f32;
-
-            frame.fill_rectangle(
-                Point::new(0.0, average_y),
-                Size::new(frame.width(), 1.0),
-                palette.background.base.text.scale_alpha(0.3),
-            );
-
             frame.fill_text(canvas::Text {
-                content: format!("~{}", (self.average_to_string)(average)),
-                position: Point::new(5.0, average_y - 2.0),
+                content: format!("{max:?}"),
+                position: Point::new(frame.width() - 5.0, 2.0),
                 color: palette.background.base.text,
-                size: Pixels(14.0),
+                size: Pixels(10.0),
                 font: Font::MONOSPACE,
-                align_y: Bottom,
+                align_x: Right.into(),
                 ..canvas::Text::default()
             });
 
-            frame.fill_rectangle(
-                Point::new(0.0, max_y),
-                Size::new(frame.width(), 1.0),
-                palette.background.base.text.scale_alpha(0.3),
+            for (i, stage) in Self::STAGES.iter().enumerate() {
+                let y = 4.0 + i as f32 * 12.0;
+
+                frame.fill_rectangle(
+                    Point::new(5.0, y + 1.0),
+                    Size::new(8.0, 8.0),
+                    Self::color(i, palette),
+                );
+
+                frame.fill_text(canvas::Text {
+                    content: stage.to_string(),
+                    position: Point::new(17.0, y),
+                    color: palette.background.base.text,
+                    size: Pixels(10.0),
+                    font: Font::MONOSPACE,
+                    ..canvas::Text::default()
+                });
+            }
+        });
+
+        let mut overlay = canvas::Frame::new(renderer, bounds.size());
+
+        if let Some((i, frame)) = hovered.and_then(|index| {
+            self.frames
+                .clone()
+                .take(amount)
+                .enumerate()
+                .find(|(_, frame)| frame.index == index)
+        }) {
+            overlay.fill_rectangle(
+                Point::new(bounds.width - bar_width * (i + 1) as f32, 0.0),
+                Size::new(bar_width, bounds.height),
+                Color::BLACK.scale_alpha(0.3),
             );
 
-            frame.fill_text(canvas::Text {
-                content: (self.to_string)(max),
-                position: Point::new(frame.width() - 5.0, max_y + 2.0),
+            let breakdown = Self::STAGES
+                .iter()
+                .zip(frame.stages())
+                .map(|(stage, duration)| format!("{stage}: {duration:?}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+
+            overlay.fill_text(canvas::Text {
+                content: format!("{:?} = {breakdown}", frame.total()),
+                position: Point::new(bounds.width / 2.0, bounds.height - 4.0),
                 color: palette.background.base.text,
                 size: Pixels(10.0),
                 font: Font::MONOSPACE,
-                align_x: Right.into(),
+                align_x: Center.into(),
+                align_y: Bottom,
                 ..canvas::Text::default()
             });
-        });
+        }
 
-        vec![geometry]
+        vec![bars, overlay.into_geometry()]
     }
 }
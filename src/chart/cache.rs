@@ -0,0 +1,90 @@
+use iced::Renderer;
+use iced::Size;
+use iced::time::{Duration, Instant};
+use iced::widget::canvas;
+
+use std::cell::Cell;
+
+use super::Edge;
+
+/// The cached geometry of a chart.
+///
+/// Clearing the cache only marks it as stale; stale geometry is redrawn at
+/// most once every [`Cache::INTERVAL`], so a burst of events costs a single
+/// redraw instead of one per event.
+///
+/// Bar charts also leave an [`Edge`] past their cached bars, where new
+/// datapoints are drawn on a layer of their own; see [`Cache::extend`].
+#[derive(Debug, Default)]
+pub struct Cache {
+    geometry: canvas::Cache,
+    is_stale: Cell<bool>,
+    drawn_at: Cell<Option<Instant>>,
+    edge: Cell<Option<Edge>>,
+}
+
+impl Cache {
+    const INTERVAL: Duration = Duration::from_millis(30);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.is_stale.set(true);
+    }
+
+    /// Marks the chart as changed by new datapoints only.
+    ///
+    /// If the cached geometry has an [`Edge`], the new datapoints are drawn
+    /// past it and nothing is redrawn until it fills up; otherwise, this is
+    /// the same as [`Cache::clear`].
+    pub fn extend(&self) {
+        if self.edge.get().is_none() {
+            self.clear();
+        }
+    }
+
+    pub(super) fn edge(&self) -> Option<Edge> {
+        self.edge.get()
+    }
+
+    pub(super) fn set_edge(&self, edge: Option<Edge>) {
+        self.edge.set(edge);
+    }
+
+    /// Returns when the stale geometry will be redrawn, if it is stale.
+    pub fn redraw_at(&self) -> Option<Instant> {
+        if !self.is_stale.get() {
+            return None;
+        }
+
+        Some(
+            self.drawn_at
+                .get()
+                .map_or_else(Instant::now, |drawn_at| drawn_at + Self::INTERVAL),
+        )
+    }
+
+    pub fn draw(
+        &self,
+        renderer: &Renderer,
+        size: Size,
+        draw: impl FnOnce(&mut canvas::Frame),
+    ) -> canvas::Geometry {
+        if self
+            .redraw_at()
+            .is_some_and(|redraw_at| redraw_at <= Instant::now())
+        {
+            self.geometry.clear();
+            self.is_stale.set(false);
+        }
+
+        self.geometry.draw(renderer, size, |frame| {
+            self.drawn_at.set(Some(Instant::now()));
+            self.edge.set(None);
+
+            draw(frame);
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use iced::widget::canvas;
+use iced::{Color, Point, Size};
+
+/// Something a chart can be drawn on: the frame of a canvas, or an image
+/// being exported.
+pub trait Surface {
+    fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color);
+
+    fn fill_triangle(&mut self, points: [Point; 3], color: Color);
+
+    fn fill_text(&mut self, text: canvas::Text);
+}
+
+impl Surface for canvas::Frame {
+    fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color) {
+        canvas::Frame::fill_rectangle(self, top_left, size, color);
+    }
+
+    fn fill_triangle(&mut self, [a, b, c]: [Point; 3], color: Color) {
+        self.fill(
+            &canvas::Path::new(|path| {
+                path.move_to(a);
+                path.line_to(b);
+                path.line_to(c);
+                path.close();
+            }),
+            color,
+        );
+    }
+
+    fn fill_text(&mut self, text: canvas::Text) {
+        canvas::Frame::fill_text(self, text);
+    }
+}
@@ -0,0 +1,234 @@
+//! Tests for the chart canvas programs.
+//!
+//! Most of them are golden-image tests of whole charts, while the rest
+//! check the geometry drawn by [`render`] and kept in the [`Cache`].
+//!
+//! Snapshots live in `tests/snapshots`. A missing snapshot fails the test,
+//! unless `COMET_RECORD_SNAPSHOTS` is set, in which case it is recorded;
+//! delete a snapshot and record it again after an intended visual change.
+//!
+//! Rendering and fonts differ between platforms, so the snapshots are only
+//! meaningful on the one they were recorded on. Until they are committed,
+//! the golden-image tests are ignored; record them with:
+//!
+//! ```text
+//! COMET_RECORD_SNAPSHOTS=1 cargo test -- --ignored
+//! ```
+use super::*;
+
+use crate::beacon::span::Span;
+use crate::core::window;
+use crate::timeline::Playhead;
+
+use iced::time::SystemTime;
+
+use iced_test::{Error, simulator};
+
+fn fixture() -> Timeline {
+    let mut timeline = Timeline::new();
+
+    let window = window::Id::unique();
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+    for i in 0..300u64 {
+        let millis = [2, 3, 2, 4, 25, 2, 3][i as usize % 7];
+
+        timeline.push(beacon::Event::SpanFinished {
+            at: start + Duration::from_millis(i * 16),
+            duration: Duration::from_micros(millis * 1_000 + i * 10),
+            span: Span::View { window },
+        });
+
+        timeline.push(beacon::Event::SpanFinished {
+            at: start + Duration::from_millis(i * 16 + 1),
+            duration: Duration::from_micros(500 + (i % 11) * 100),
+            span: Span::Layout { window },
+        });
+    }
+
+    timeline
+}
+
+fn snapshot(stage: Stage, settings: Settings, name: &str) -> Result<(), Error> {
+    let timeline = fixture();
+    let cache = Cache::default();
+
+    let chart = performance(
+        stage,
+        &cache,
+        &timeline,
+        Playhead::Live,
+        Playhead::Live,
+        settings,
+        &Baseline::default(),
+    );
+
+    let path = format!("tests/snapshots/{name}.png");
+
+    assert!(
+        std::path::Path::new(&path).exists()
+            || std::env::var_os("COMET_RECORD_SNAPSHOTS").is_some(),
+        "{path} is missing; run the tests with COMET_RECORD_SNAPSHOTS=1 to record it"
+    );
+
+    let mut ui = simulator(chart);
+    let snapshot = ui.snapshot(&Theme::CatppuccinMocha)?;
+
+    assert!(
+        snapshot.matches_image(&path)?,
+        "{name} chart does not match its snapshot"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "snapshots are not recorded yet"]
+fn view_chart() -> Result<(), Error> {
+    snapshot(Stage::View, Settings::default(), "view")
+}
+
+#[test]
+#[ignore = "snapshots are not recorded yet"]
+fn layout_chart() -> Result<(), Error> {
+    snapshot(Stage::Layout, Settings::default(), "layout")
+}
+
+#[test]
+#[ignore = "snapshots are not recorded yet"]
+fn zoomed_in_view_chart() -> Result<(), Error> {
+    let settings = Settings {
+        zoom: Zoom::default().increment().increment(),
+        ..Settings::default()
+    };
+
+    snapshot(Stage::View, settings, "view-zoomed")
+}
+
+#[test]
+#[ignore = "snapshots are not recorded yet"]
+fn view_histogram() -> Result<(), Error> {
+    let settings = Settings {
+        mode: Mode::Histogram {
+            buckets: Buckets::default(),
+        },
+        ..Settings::default()
+    };
+
+    snapshot(Stage::View, settings, "view-histogram")
+}
+
+#[test]
+#[ignore = "snapshots are not recorded yet"]
+fn view_chart_with_anomalies() -> Result<(), Error> {
+    let settings = Settings {
+        anomalies: true,
+        ..Settings::default()
+    };
+
+    snapshot(Stage::View, settings, "view-anomalies")
+}
+
+/// A [`Surface`] that only checks everything drawn has a finite position.
+struct Finite;
+
+impl Surface for Finite {
+    fn fill_rectangle(&mut self, top_left: Point, size: Size, _color: Color) {
+        assert!(top_left.x.is_finite() && top_left.y.is_finite());
+        assert!(size.width.is_finite() && size.height.is_finite());
+    }
+
+    fn fill_triangle(&mut self, points: [Point; 3], _color: Color) {
+        assert!(
+            points
+                .iter()
+                .all(|point| point.x.is_finite() && point.y.is_finite())
+        );
+    }
+
+    fn fill_text(&mut self, text: canvas::Text) {
+        assert!(text.position.x.is_finite() && text.position.y.is_finite());
+    }
+}
+
+#[test]
+fn render_zero_durations() {
+    let mut timeline = Timeline::new();
+    let window = window::Id::unique();
+
+    for i in 0..10 {
+        timeline.push(beacon::Event::SpanFinished {
+            at: SystemTime::UNIX_EPOCH + Duration::from_millis(i * 16),
+            duration: Duration::ZERO,
+            span: Span::View { window },
+        });
+    }
+
+    render(
+        &mut Finite,
+        Size::new(800.0, 270.0),
+        Stage::View,
+        Context {
+            timeline: &timeline,
+            offset: Playhead::Live,
+            selection: Playhead::Live,
+            settings: Settings::default(),
+            baseline: &Baseline::default(),
+        },
+        Theme::CatppuccinMocha.palette(),
+    );
+}
+
+#[test]
+fn cached_bars_stay_put_past_the_edge() {
+    let mut timeline = fixture();
+    let cache = Cache::default();
+    let bounds = Size::new(800.0, 270.0);
+
+    view_chart(&cache, &timeline).settle(bounds, 0);
+
+    let before = columns(&cache, &timeline, bounds);
+    let window = timeline.windows()[0];
+
+    timeline.push(beacon::Event::SpanFinished {
+        at: SystemTime::UNIX_EPOCH
+            + Duration::from_secs(1_700_000_000)
+            + Duration::from_millis(4_800),
+        duration: Duration::from_millis(3),
+        span: Span::View { window },
+    });
+
+    let after = columns(&cache, &timeline, bounds);
+
+    assert_eq!(after.len(), before.len() + 1);
+    assert_eq!(after[1..], before[..]);
+    assert!(!view_chart(&cache, &timeline).is_edge_full(bounds, 0));
+}
+
+fn view_chart<'a>(
+    cache: &'a Cache,
+    timeline: &'a Timeline,
+) -> BarChart<
+    'a,
+    impl Iterator<Item = (timeline::Index, SystemTime, Duration)> + Clone + 'a,
+    Duration,
+    Duration,
+> {
+    stage_chart(
+        Stage::View,
+        cache,
+        timeline,
+        Playhead::Live,
+        Playhead::Live,
+        Settings::default(),
+        None,
+    )
+}
+
+fn columns(cache: &Cache, timeline: &Timeline, bounds: Size) -> Vec<(timeline::Index, f32)> {
+    view_chart(cache, timeline)
+        .columns(bounds.width, 0)
+        .into_iter()
+        .map(|column| (column.index, column.x))
+        .collect()
+}
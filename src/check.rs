@@ -0,0 +1,361 @@
+use crate::beacon;
+use crate::session;
+use crate::stats::{self, Statistics};
+use crate::summary;
+use crate::timeline::{Playhead, Timeline};
+
+use iced::futures::StreamExt;
+use iced::time::Duration;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Where the events to check come from.
+#[derive(Debug, Clone)]
+pub enum Source {
+    Session(PathBuf),
+    Live(Duration),
+}
+
+/// A previous session to compare against, failing when any stage gets
+/// slower than its baseline by more than `tolerance` percent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Baseline {
+    pub path: PathBuf,
+    pub tolerance: f64,
+}
+
+/// A performance budget, like `p95 update < 4ms` or `max frame <= 33ms`.
+///
+/// Stages are matched case-insensitively against the stage names shown in
+/// comet; `frame` stands for the total time of each frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Threshold {
+    statistic: Statistic,
+    stage: String,
+    inclusive: bool,
+    limit: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Statistic {
+    Mean,
+    Min,
+    Max,
+    P50,
+    P95,
+    P99,
+}
+
+impl Statistic {
+    fn of(self, statistics: &Statistics) -> Duration {
+        match self {
+            Statistic::Mean => statistics.mean,
+            Statistic::Min => statistics.min,
+            Statistic::Max => statistics.max,
+            Statistic::P50 => statistics.p50,
+            Statistic::P95 => statistics.p95,
+            Statistic::P99 => statistics.p99,
+        }
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(threshold: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("invalid threshold: {threshold} (expected e.g. \"p95 update < 4ms\")");
+
+        let mut parts = threshold.split_whitespace();
+
+        let (Some(statistic), Some(stage), Some(operator), Some(limit), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return Err(invalid());
+        };
+
+        let statistic = match statistic.to_lowercase().as_str() {
+            "mean" | "avg" | "average" => Statistic::Mean,
+            "min" => Statistic::Min,
+            "max" => Statistic::Max,
+            "p50" | "median" => Statistic::P50,
+            "p95" => Statistic::P95,
+            "p99" => Statistic::P99,
+            _ => return Err(invalid()),
+        };
+
+        let inclusive = match operator {
+            "<" => false,
+            "<=" => true,
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            statistic,
+            stage: stage.to_lowercase(),
+            inclusive,
+            limit: duration(limit).ok_or_else(invalid)?,
+        })
+    }
+}
+
+impl fmt::Display for Threshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let statistic = match self.statistic {
+            Statistic::Mean => "mean",
+            Statistic::Min => "min",
+            Statistic::Max => "max",
+            Statistic::P50 => "p50",
+            Statistic::P95 => "p95",
+            Statistic::P99 => "p99",
+        };
+
+        let operator = if self.inclusive { "<=" } else { "<" };
+
+        write!(f, "{statistic} {} {operator} {:?}", self.stage, self.limit)
+    }
+}
+
+/// Parses durations like `4ms`, `250us` or `1.5s`.
+fn duration(duration: &str) -> Option<Duration> {
+    let (amount, unit) = duration.split_at(duration.find(|c: char| c.is_alphabetic())?);
+    let amount: f64 = amount.parse().ok().filter(|amount: &f64| *amount >= 0.0)?;
+
+    let seconds = match unit {
+        "s" => amount,
+        "ms" => amount / 1_000.0,
+        "us" | "µs" => amount / 1_000_000.0,
+        "ns" => amount / 1_000_000_000.0,
+        _ => return None,
+    };
+
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Evaluates the thresholds against the events of the [`Source`], and
+/// compares them with the [`Baseline`], if any, printing a
+/// [`summary::Summary`] with the results to stdout.
+///
+/// Returns whether every threshold was met and no stage regressed.
+pub fn run(
+    source: &Source,
+    thresholds: &[Threshold],
+    baseline: Option<&Baseline>,
+) -> Result<bool, String> {
+    let mut recorded = summary::Capture::new();
+
+    let events: Vec<_> = match source {
+        Source::Session(path) => session::read(path)
+            .map_err(|error| format!("failed to read {}: {error}", path.display()))?
+            .into_iter()
+            .filter_map(|entry| {
+                if let session::Entry::Connected { .. } = entry {
+                    recorded.push_entry(entry);
+                    return None;
+                }
+
+                entry.into_event()
+            })
+            .collect(),
+        Source::Live(duration) => capture(*duration).map_err(|error| error.to_string())?,
+    };
+
+    for event in &events {
+        recorded.push(event);
+    }
+
+    let statistics = statistics(events);
+
+    let results: Vec<_> = thresholds
+        .iter()
+        .map(|threshold| threshold.evaluate(statistics.get(&threshold.stage)))
+        .collect();
+
+    let comparisons = match baseline {
+        Some(baseline) => compare(baseline, &statistics)?,
+        None => Vec::new(),
+    };
+
+    let passed = results.iter().all(|result| result.pass)
+        && comparisons.iter().all(|comparison| comparison.pass);
+
+    let source = match source {
+        Source::Session(path) => Some(path.as_path()),
+        Source::Live(_) => None,
+    };
+
+    let mut summary = recorded.summary("check", source);
+    summary.thresholds = results;
+    summary.baseline = comparisons;
+    summary.passed = Some(passed);
+    summary.print();
+
+    Ok(passed)
+}
+
+/// Computes the statistics of every stage, keyed by their lowercase name,
+/// along with the total time of each `frame`.
+fn statistics(events: Vec<beacon::Event>) -> BTreeMap<String, Statistics> {
+    let mut collector = stats::Collector::new();
+    let mut timeline = Timeline::new();
+
+    for event in events {
+        collector.push(&event);
+        timeline.push(event);
+    }
+
+    let mut statistics: BTreeMap<String, Statistics> = collector
+        .statistics()
+        .into_iter()
+        .map(|(stage, statistics)| (stage.to_lowercase(), statistics))
+        .collect();
+
+    if let Some(frames) =
+        Statistics::new(timeline.frames(Playhead::Live).map(|frame| frame.total()))
+    {
+        let _ = statistics.insert(String::from("frame"), frames);
+    }
+
+    statistics
+}
+
+/// Compares the mean, p50 and p95 of every stage present in both the
+/// [`Baseline`] and the checked events, logging a table to stderr.
+///
+/// Fails when the two share no stages at all, since nothing was compared.
+fn compare(
+    baseline: &Baseline,
+    statistics: &BTreeMap<String, Statistics>,
+) -> Result<Vec<summary::Comparison>, String> {
+    const STATISTICS: &[(Statistic, &str)] = &[
+        (Statistic::Mean, "mean"),
+        (Statistic::P50, "p50"),
+        (Statistic::P95, "p95"),
+    ];
+
+    let events = session::read(&baseline.path)
+        .map_err(|error| format!("failed to read {}: {error}", baseline.path.display()))?
+        .into_iter()
+        .filter_map(session::Entry::into_event)
+        .collect();
+
+    let expected = self::statistics(events);
+    let mut comparisons = Vec::new();
+
+    log::info!(
+        "{:<20} {:<5} {:>12} {:>12} {:>8}",
+        "stage",
+        "stat",
+        "baseline",
+        "observed",
+        "change"
+    );
+
+    for (stage, expected) in &expected {
+        let Some(observed) = statistics.get(stage) else {
+            log::warn!("{stage} is missing from the checked events; skipping it");
+            continue;
+        };
+
+        for (statistic, name) in STATISTICS {
+            let expected = summary::micros(statistic.of(expected));
+            let observed = summary::micros(statistic.of(observed));
+
+            let change = if expected > 0.0 {
+                (observed - expected) / expected * 100.0
+            } else {
+                0.0
+            };
+
+            let pass = observed <= expected * (1.0 + baseline.tolerance / 100.0);
+
+            log::info!(
+                "{stage:<20} {name:<5} {expected:>10.1}us {observed:>10.1}us {change:>+7.1}%{}",
+                if pass { "" } else { " REGRESSION" }
+            );
+
+            comparisons.push(summary::Comparison {
+                stage: stage.clone(),
+                statistic: *name,
+                baseline_us: expected,
+                observed_us: observed,
+                change_pct: change,
+                pass,
+            });
+        }
+    }
+
+    if comparisons.is_empty() {
+        return Err(format!(
+            "{} has no stages in common with the checked events",
+            baseline.path.display()
+        ));
+    }
+
+    Ok(comparisons)
+}
+
+impl Threshold {
+    /// Evaluates the threshold against the statistics of its stage, which
+    /// fails when there are no samples at all.
+    fn evaluate(&self, statistics: Option<&Statistics>) -> summary::Threshold {
+        let value = statistics.map(|statistics| self.statistic.of(statistics));
+
+        let pass = value.is_some_and(|value| {
+            if self.inclusive {
+                value <= self.limit
+            } else {
+                value < self.limit
+            }
+        });
+
+        summary::Threshold {
+            name: self.to_string(),
+            limit_us: summary::micros(self.limit),
+            observed_us: value.map(summary::micros),
+            samples: statistics.map_or(0, |statistics| statistics.count),
+            pass,
+        }
+    }
+}
+
+/// Runs the beacon server for the given amount of time, collecting every
+/// event reported by the connected application.
+fn capture(duration: Duration) -> std::io::Result<Vec<beacon::Event>> {
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    Ok(runtime.block_on(async {
+        let mut events = std::pin::pin!(beacon::run());
+        let mut captured = Vec::new();
+        let mut timeout = std::pin::pin!(tokio::time::sleep(duration));
+        let mut shutdown = std::pin::pin!(tokio::signal::ctrl_c());
+
+        log::info!("Capturing events for {duration:?}...");
+
+        loop {
+            let event = tokio::select! {
+                event = events.next() => event,
+                _ = &mut timeout => None,
+                _ = &mut shutdown => None,
+            };
+
+            match event {
+                Some(beacon::Event::AlreadyRunning { .. }) => {
+                    log::warn!("comet is already running. Nothing to capture!");
+                    break;
+                }
+                Some(event) => captured.push(event),
+                None => break,
+            }
+        }
+
+        captured
+    }))
+}
@@ -0,0 +1,366 @@
+use crate::check;
+use crate::export;
+use crate::otlp;
+
+use iced::time::Duration;
+
+use std::env;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Gui(Options),
+    Daemon {
+        output: PathBuf,
+    },
+    Stress {
+        events_per_second: u32,
+        duration: Option<Duration>,
+    },
+    Export {
+        session: PathBuf,
+        format: export::Format,
+        output: PathBuf,
+    },
+    Check {
+        source: check::Source,
+        thresholds: Vec<check::Threshold>,
+        baseline: Option<check::Baseline>,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub stdin: bool,
+    pub auto_save: Option<PathBuf>,
+    pub auto_save_every: Option<Duration>,
+    pub attach: Option<PathBuf>,
+    pub open: Option<PathBuf>,
+    pub frame_budget: Option<Duration>,
+    pub tail: Option<Duration>,
+    pub retention: Option<Duration>,
+    pub memory_limit: Option<usize>,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub speed: Option<f64>,
+    pub otlp: Option<otlp::Endpoint>,
+    pub baseline: Option<PathBuf>,
+    pub compare: Option<(PathBuf, PathBuf)>,
+}
+
+impl Options {
+    /// Whether comet only browses session files, without running a beacon
+    /// server for live applications.
+    pub fn is_offline(&self) -> bool {
+        self.attach.is_some()
+            || self.open.is_some()
+            || self.replay.is_some()
+            || self.compare.is_some()
+    }
+}
+
+pub fn parse() -> Result<Command, String> {
+    let mut arguments = env::args().skip(1).peekable();
+
+    match arguments.peek().map(String::as_str) {
+        Some("daemon") => {
+            let _ = arguments.next();
+            let output = value("daemon", arguments.next())?;
+
+            if let Some(argument) = arguments.next() {
+                return Err(format!("unknown argument: {argument}"));
+            }
+
+            Ok(Command::Daemon {
+                output: PathBuf::from(output),
+            })
+        }
+        Some("stress") => {
+            let _ = arguments.next();
+
+            let mut events_per_second = 1_000;
+            let mut duration = None;
+
+            while let Some(argument) = arguments.next() {
+                match argument.as_str() {
+                    "--events-per-second" => {
+                        let value = value(&argument, arguments.next())?;
+
+                        events_per_second = value
+                            .parse()
+                            .map_err(|_| format!("invalid events per second: {value}"))?;
+                    }
+                    "--duration" => {
+                        let value = value(&argument, arguments.next())?;
+
+                        duration =
+                            Some(Duration::from_secs(value.parse().map_err(|_| {
+                                format!("invalid amount of seconds: {value}")
+                            })?));
+                    }
+                    _ => {
+                        return Err(format!("unknown argument: {argument}"));
+                    }
+                }
+            }
+
+            Ok(Command::Stress {
+                events_per_second,
+                duration,
+            })
+        }
+        Some("export") => {
+            let _ = arguments.next();
+            let session = PathBuf::from(value("export", arguments.next())?);
+
+            let mut format = export::Format::Csv;
+            let mut output = None;
+
+            while let Some(argument) = arguments.next() {
+                match argument.as_str() {
+                    "--format" => {
+                        format = value(&argument, arguments.next())?.parse()?;
+                    }
+                    "--output" => {
+                        output = Some(PathBuf::from(value(&argument, arguments.next())?));
+                    }
+                    _ => {
+                        return Err(format!("unknown argument: {argument}"));
+                    }
+                }
+            }
+
+            Ok(Command::Export {
+                output: output.unwrap_or_else(|| session.with_extension(format.extension())),
+                session,
+                format,
+            })
+        }
+        Some("check") => {
+            let _ = arguments.next();
+
+            let mut session = None;
+            let mut live = None;
+            let mut thresholds = Vec::new();
+            let mut baseline = None;
+            let mut tolerance = None;
+
+            while let Some(argument) = arguments.next() {
+                match argument.as_str() {
+                    "--threshold" => {
+                        thresholds.push(value(&argument, arguments.next())?.parse()?);
+                    }
+                    "--baseline" => {
+                        baseline = Some(PathBuf::from(value(&argument, arguments.next())?));
+                    }
+                    "--tolerance" => {
+                        let value = value(&argument, arguments.next())?;
+
+                        tolerance = Some(
+                            value
+                                .trim_end_matches('%')
+                                .parse::<f64>()
+                                .ok()
+                                .filter(|tolerance| *tolerance >= 0.0)
+                                .ok_or_else(|| format!("invalid tolerance: {value}"))?,
+                        );
+                    }
+                    "--live" => {
+                        let value = value(&argument, arguments.next())?;
+
+                        live =
+                            Some(Duration::from_secs(value.parse().map_err(|_| {
+                                format!("invalid amount of seconds: {value}")
+                            })?));
+                    }
+                    _ if session.is_none() && !argument.starts_with("--") => {
+                        session = Some(PathBuf::from(argument));
+                    }
+                    _ => {
+                        return Err(format!("unknown argument: {argument}"));
+                    }
+                }
+            }
+
+            let source = match (session, live) {
+                (Some(session), None) => check::Source::Session(session),
+                (None, Some(duration)) => check::Source::Live(duration),
+                _ => {
+                    return Err(String::from(
+                        "check requires either a session file or --live <seconds>",
+                    ));
+                }
+            };
+
+            let baseline = match (baseline, tolerance) {
+                (Some(path), tolerance) => Some(check::Baseline {
+                    path,
+                    tolerance: tolerance.unwrap_or(10.0),
+                }),
+                (None, Some(_)) => {
+                    return Err(String::from("--tolerance requires a --baseline session"));
+                }
+                (None, None) => None,
+            };
+
+            if thresholds.is_empty() && baseline.is_none() {
+                return Err(String::from(
+                    "check requires at least one --threshold or a --baseline",
+                ));
+            }
+
+            Ok(Command::Check {
+                source,
+                thresholds,
+                baseline,
+            })
+        }
+        Some("open") => {
+            let _ = arguments.next();
+            let session = value("open", arguments.next())?;
+
+            let mut options = Options::parse(arguments)?;
+            options.open = Some(PathBuf::from(session));
+
+            Ok(Command::Gui(options))
+        }
+        Some("attach") => {
+            let _ = arguments.next();
+            let session = value("attach", arguments.next())?;
+
+            let mut options = Options::parse(arguments)?;
+            options.attach = Some(PathBuf::from(session));
+
+            Ok(Command::Gui(options))
+        }
+        _ => Ok(Command::Gui(Options::parse(arguments)?)),
+    }
+}
+
+impl Options {
+    fn parse(mut arguments: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--stdin" => {
+                    options.stdin = true;
+                }
+                "--auto-save" => {
+                    options.auto_save = Some(PathBuf::from(value(&argument, arguments.next())?));
+                }
+                "--auto-save-every" => {
+                    let value = value(&argument, arguments.next())?;
+
+                    let seconds = value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|minutes| *minutes > 0)
+                        .and_then(|minutes| minutes.checked_mul(60))
+                        .ok_or_else(|| format!("invalid amount of minutes: {value}"))?;
+
+                    options.auto_save_every = Some(Duration::from_secs(seconds));
+                }
+                "--frame-budget" => {
+                    let value = value(&argument, arguments.next())?;
+
+                    let budget = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|milliseconds| *milliseconds > 0.0)
+                        .and_then(|milliseconds| {
+                            Duration::try_from_secs_f64(milliseconds / 1_000.0).ok()
+                        })
+                        .ok_or_else(|| format!("invalid amount of milliseconds: {value}"))?;
+
+                    options.frame_budget = Some(budget);
+                }
+                "--tail" => {
+                    let value = value(&argument, arguments.next())?;
+
+                    let seconds: u64 = value
+                        .parse()
+                        .ok()
+                        .filter(|seconds| *seconds > 0)
+                        .ok_or_else(|| format!("invalid amount of seconds: {value}"))?;
+
+                    options.tail = Some(Duration::from_secs(seconds));
+                }
+                "--retention" => {
+                    let value = value(&argument, arguments.next())?;
+
+                    let seconds = value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|minutes| *minutes > 0)
+                        .and_then(|minutes| minutes.checked_mul(60))
+                        .ok_or_else(|| format!("invalid amount of minutes: {value}"))?;
+
+                    options.retention = Some(Duration::from_secs(seconds));
+                }
+                "--memory-limit" => {
+                    let value = value(&argument, arguments.next())?;
+
+                    let bytes = value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|megabytes| *megabytes > 0)
+                        .and_then(|megabytes| megabytes.checked_mul(1_000_000))
+                        .ok_or_else(|| format!("invalid amount of megabytes: {value}"))?;
+
+                    options.memory_limit = Some(bytes);
+                }
+                "--record" => {
+                    options.record = Some(PathBuf::from(value(&argument, arguments.next())?));
+                }
+                "--replay" => {
+                    options.replay = Some(PathBuf::from(value(&argument, arguments.next())?));
+                }
+                "--speed" => {
+                    let value = value(&argument, arguments.next())?;
+
+                    let speed: f64 = value
+                        .parse()
+                        .ok()
+                        .filter(|speed: &f64| speed.is_finite() && *speed > 0.0)
+                        .ok_or_else(|| format!("invalid speed: {value}"))?;
+
+                    options.speed = Some(speed);
+                }
+                "--baseline" => {
+                    options.baseline = Some(PathBuf::from(value(&argument, arguments.next())?));
+                }
+                "--compare" => {
+                    let before = value(&argument, arguments.next())?;
+                    let after = value(&argument, arguments.next())?;
+
+                    options.compare = Some((PathBuf::from(before), PathBuf::from(after)));
+                }
+                "--otlp" => {
+                    options.otlp = Some(value(&argument, arguments.next())?.parse()?);
+                }
+                _ => {
+                    return Err(format!("unknown argument: {argument}"));
+                }
+            }
+        }
+
+        if options.speed.is_some() && options.replay.is_none() {
+            return Err(String::from("--speed requires --replay"));
+        }
+
+        if options.record.is_some() && options.is_offline() {
+            return Err(String::from("--record requires a live connection"));
+        }
+
+        if options.auto_save_every.is_some() && options.auto_save.is_none() {
+            return Err(String::from("--auto-save-every requires --auto-save"));
+        }
+
+        Ok(options)
+    }
+}
+
+fn value(argument: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("missing value for {argument}"))
+}
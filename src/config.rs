@@ -0,0 +1,91 @@
+use crate::chart;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// The preferences of the user that persist between runs of comet.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The name of the built-in theme of comet, or `System` to follow the
+    /// light or dark mode of the system, until an application provides its
+    /// own.
+    pub theme: Option<String>,
+    /// Whether the palette of the application replaces the theme whenever
+    /// it changes, which is the default.
+    pub follow_app: Option<bool>,
+    pub zoom: Option<chart::Zoom>,
+    /// The frame budget, in milliseconds.
+    pub frame_budget: Option<f64>,
+    pub window: Option<Geometry>,
+    /// The position of the last board used.
+    pub board: usize,
+    /// The keyboard shortcuts overriding the defaults, by action name.
+    pub keys: BTreeMap<String, String>,
+}
+
+/// The position and size of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Geometry {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Returns the directory where comet stores its configuration, if the
+/// platform has one.
+pub fn directory() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    base.map(|base| base.join("comet"))
+}
+
+/// Loads the TOML configuration file with the given name, if it exists and
+/// can be parsed.
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let path = directory()?.join(name);
+    let contents = fs::read_to_string(&path).ok()?;
+
+    match toml::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            log::warn!("Failed to parse {}: {error}", path.display());
+            None
+        }
+    }
+}
+
+/// Saves the TOML configuration file with the given name, logging any
+/// errors.
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let Some(directory) = directory() else {
+        return;
+    };
+
+    let path = directory.join(name);
+
+    let result = fs::create_dir_all(&directory).and_then(|_| {
+        let contents = toml::to_string_pretty(value).map_err(io::Error::other)?;
+
+        fs::write(&path, contents)
+    });
+
+    if let Err(error) = result {
+        log::error!("Failed to save {}: {error}", path.display());
+    }
+}
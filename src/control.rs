@@ -0,0 +1,88 @@
+use iced::futures::Stream;
+use iced::futures::channel::mpsc;
+use iced::time::Duration;
+
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Pause,
+    Live,
+    Seek(Duration),
+    Export(Format, PathBuf),
+    Screenshot(PathBuf),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+}
+
+pub fn listen() -> impl Stream<Item = Command> {
+    let (sender, receiver) = mpsc::unbounded();
+
+    let _ = thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match line.parse() {
+                Ok(command) => {
+                    if sender.unbounded_send(command).is_err() {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    log::warn!("{error}");
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut parts = line.split_whitespace();
+
+        let command = match (parts.next(), parts.next(), parts.next()) {
+            (Some("pause"), None, _) => Command::Pause,
+            (Some("live"), None, _) => Command::Live,
+            (Some("seek"), Some(time), None) => {
+                let seconds = time
+                    .trim_end_matches('s')
+                    .parse()
+                    .map_err(|_| format!("invalid time: {time}"))?;
+
+                Command::Seek(
+                    Duration::try_from_secs_f64(seconds)
+                        .map_err(|_| format!("invalid time: {time}"))?,
+                )
+            }
+            (Some("export"), Some("csv"), Some(path)) => {
+                Command::Export(Format::Csv, PathBuf::from(path))
+            }
+            (Some("screenshot"), Some(path), None) => Command::Screenshot(PathBuf::from(path)),
+            _ => {
+                return Err(format!("unknown command: {line}"));
+            }
+        };
+
+        if parts.next().is_some() {
+            return Err(format!("unknown command: {line}"));
+        }
+
+        Ok(command)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::beacon;
+use crate::session;
+use crate::summary;
+
+use iced::futures::StreamExt;
+
+use std::io;
+use std::path::Path;
+
+/// Runs the beacon server without a window, recording every event into the
+/// session file at `output` so a GUI can attach to it later.
+///
+/// A [`summary::Summary`] of the capture is printed to stdout on exit.
+pub fn run(output: &Path) -> io::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    let mut capture = summary::Capture::new();
+
+    let result = runtime.block_on(async {
+        let mut recorder = session::Recorder::append(output)?;
+        // Events already waiting are recorded together, with a single flush
+        let mut events = std::pin::pin!(beacon::run().ready_chunks(1_000));
+
+        let mut shutdown = std::pin::pin!(tokio::signal::ctrl_c());
+
+        log::info!("Recording to {}", output.display());
+
+        'listen: loop {
+            let batch = tokio::select! {
+                batch = events.next() => batch,
+                _ = &mut shutdown => None,
+            };
+
+            let Some(batch) = batch else {
+                break;
+            };
+
+            for event in batch {
+                match &event {
+                    beacon::Event::Connected { name, .. } => {
+                        log::info!("{name} connected");
+                    }
+                    beacon::Event::Disconnected { .. } => {
+                        log::info!("Client disconnected");
+                    }
+                    beacon::Event::AlreadyRunning { .. } => {
+                        log::warn!("comet is already running. Exiting...");
+                        break 'listen;
+                    }
+                    _ => {}
+                }
+
+                recorder.record(&event)?;
+                capture.push(&event);
+
+                if let beacon::Event::QuitRequested { .. } = event {
+                    log::info!("Quit requested. Exiting...");
+                    break 'listen;
+                }
+            }
+
+            recorder.flush()?;
+        }
+
+        recorder.flush()
+    });
+
+    capture.summary("daemon", Some(output)).print();
+
+    result
+}
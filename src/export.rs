@@ -0,0 +1,482 @@
+use crate::beacon;
+use crate::beacon::span::Span;
+use crate::chart;
+use crate::session;
+use crate::stats;
+use crate::summary;
+use crate::timeline::{Playhead, Timeline};
+
+use iced::alignment;
+use iced::time::SystemTime;
+use iced::widget::{self, canvas};
+use iced::window;
+use iced::{Color, Point, Size, Theme};
+
+use serde_json::json;
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Trace,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+            Format::Trace => "trace.json",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "trace" => Ok(Format::Trace),
+            _ => Err(format!(
+                "unknown format: {format} (expected csv, json or trace)"
+            )),
+        }
+    }
+}
+
+/// Converts the session file at `input` into the given [`Format`]:
+///
+/// - [`Format::Csv`] writes a row per span.
+/// - [`Format::Json`] writes a [`summary::Summary`] with per-stage statistics.
+/// - [`Format::Trace`] writes a Chrome trace, loadable in Perfetto.
+pub fn session(input: &Path, format: Format, output: &Path) -> io::Result<()> {
+    let entries = session::read(input)?;
+
+    match format {
+        Format::Csv => {
+            let mut timeline = Timeline::new();
+
+            for event in entries.into_iter().filter_map(session::Entry::into_event) {
+                timeline.push(event);
+            }
+
+            csv(&timeline, output)
+        }
+        Format::Json => {
+            let mut capture = summary::Capture::new();
+
+            for entry in entries {
+                capture.push_entry(entry);
+            }
+
+            let mut file = BufWriter::new(File::create(output)?);
+
+            serde_json::to_writer_pretty(&mut file, &capture.summary("export", Some(input)))?;
+            file.flush()
+        }
+        Format::Trace => trace(
+            entries.into_iter().filter_map(session::Entry::into_event),
+            output,
+        ),
+    }
+}
+
+pub fn csv(timeline: &Timeline, path: &Path) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(file, "index,at,stage,duration_us,details")?;
+
+    for (index, event) in timeline.seek_with_index(Playhead::Live).rev() {
+        let beacon::Event::SpanFinished { at, duration, span } = event else {
+            continue;
+        };
+
+        let details = match span {
+            Span::Update { .. } => timeline.message(index).unwrap_or_default(),
+            Span::Custom { name } => name.as_str(),
+            _ => "",
+        };
+
+        writeln!(
+            file,
+            "{index},{at:.6},{stage},{duration},\"{details}\"",
+            at = seconds(*at),
+            stage = stage(span),
+            duration = duration.as_micros(),
+            details = details.replace('"', "\"\""),
+        )?;
+    }
+
+    file.flush()
+}
+
+/// Writes every update message in the timeline as a CSV row, oldest first.
+pub fn messages(timeline: &Timeline, path: &Path) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(file, "number,at,duration_us,tasks,subscriptions,message")?;
+
+    for update in timeline.updates(Playhead::Live).rev() {
+        writeln!(
+            file,
+            "{number},{at:.6},{duration},{tasks},{subscriptions},\"{message}\"",
+            number = update.number,
+            at = seconds(update.at),
+            duration = update.duration.as_micros(),
+            tasks = update.tasks,
+            subscriptions = update.subscriptions,
+            message = update.message.replace('"', "\"\""),
+        )?;
+    }
+
+    file.flush()
+}
+
+/// Writes the spans of the given events in the Chrome trace event format.
+///
+/// Application-wide spans share a thread, while every window gets one of
+/// its own.
+pub fn trace(events: impl IntoIterator<Item = beacon::Event>, path: &Path) -> io::Result<()> {
+    let mut windows = HashMap::new();
+
+    let trace_events: Vec<_> = events
+        .into_iter()
+        .filter_map(|event| {
+            let beacon::Event::SpanFinished { at, duration, span } = event else {
+                return None;
+            };
+
+            let window = match &span {
+                Span::View { window }
+                | Span::Layout { window }
+                | Span::Interact { window }
+                | Span::Draw { window }
+                | Span::Present { window, .. } => Some(*window),
+                _ => None,
+            };
+
+            let thread = window.map_or(1, |window| {
+                let next = windows.len() + 2;
+
+                *windows.entry(window).or_insert(next)
+            });
+
+            let mut args = json!({});
+
+            if let Some(window) = window {
+                args["window"] = json!(format!("{window:?}"));
+            }
+
+            if let Span::Update { message, .. } = &span {
+                args["message"] = json!(message);
+            }
+
+            let start = at.checked_sub(duration).unwrap_or(at);
+
+            Some(json!({
+                "name": stats::stage(&span),
+                "cat": stage(&span),
+                "ph": "X",
+                "ts": seconds(start) * 1_000_000.0,
+                "dur": duration.as_secs_f64() * 1_000_000.0,
+                "pid": 1,
+                "tid": thread,
+                "args": args,
+            }))
+        })
+        .collect();
+
+    let mut file = BufWriter::new(File::create(path)?);
+
+    serde_json::to_writer(
+        &mut file,
+        &json!({
+            "traceEvents": trace_events,
+            "displayTimeUnit": "ms",
+        }),
+    )?;
+
+    file.flush()
+}
+
+/// Writes a Markdown performance report of the whole [`Timeline`], ready
+/// to be pasted into an issue.
+pub fn report(
+    path: &Path,
+    name: &str,
+    version: Option<&str>,
+    timeline: &Timeline,
+) -> io::Result<()> {
+    const WORST_FRAMES: usize = 10;
+
+    let mut file = BufWriter::new(File::create(path)?);
+    let mut collector = stats::Collector::new();
+
+    for event in timeline.events(*timeline.range().start()) {
+        collector.push(event);
+    }
+
+    let format = |time: SystemTime| {
+        chrono::DateTime::<chrono::Local>::from(time)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    };
+
+    let start = timeline.start();
+    let end = timeline.time_at(Playhead::Live);
+
+    writeln!(file, "# Performance report: {name}")?;
+    writeln!(file)?;
+
+    if let Some(version) = version {
+        writeln!(file, "- **Version:** {version}")?;
+    }
+
+    if let (Some(start), Some(end)) = (start, end) {
+        writeln!(
+            file,
+            "- **Session:** {} to {} ({:.1}s)",
+            format(start),
+            format(end),
+            end.duration_since(start).unwrap_or_default().as_secs_f64()
+        )?;
+    }
+
+    writeln!(file, "- **Events:** {}", timeline.len())?;
+
+    let updates = timeline
+        .update_rate(Playhead::Live)
+        .map(|bucket| bucket.total);
+    let (seconds, total, peak) = updates.fold((0, 0, 0), |(seconds, total, peak), updates| {
+        (seconds + 1, total + updates, peak.max(updates))
+    });
+
+    if seconds > 0 {
+        writeln!(
+            file,
+            "- **Message rate:** {:.1} msg/s on average, {peak} msg/s at peak",
+            total as f64 / seconds as f64
+        )?;
+    }
+
+    writeln!(file)?;
+    writeln!(file, "## Stages")?;
+    writeln!(file)?;
+    writeln!(file, "| Stage | Count | Mean | p50 | p95 | p99 | Max |")?;
+    writeln!(file, "|---|---:|---:|---:|---:|---:|---:|")?;
+
+    for (stage, statistics) in collector.statistics() {
+        writeln!(
+            file,
+            "| {stage} | {} | {:?} | {:?} | {:?} | {:?} | {:?} |",
+            statistics.count,
+            statistics.mean,
+            statistics.p50,
+            statistics.p95,
+            statistics.p99,
+            statistics.max,
+        )?;
+    }
+
+    let mut frames: Vec<_> = timeline.frames(Playhead::Live).collect();
+    frames.sort_by_key(|frame| std::cmp::Reverse(frame.total()));
+
+    if !frames.is_empty() {
+        writeln!(file)?;
+        writeln!(file, "## Worst frames")?;
+        writeln!(file)?;
+        writeln!(
+            file,
+            "| Index | Total | Update | View | Layout | Interact | Draw | Present |"
+        )?;
+        writeln!(file, "|---:|---:|---:|---:|---:|---:|---:|---:|")?;
+
+        for frame in frames.iter().take(WORST_FRAMES) {
+            let [update, view, layout, interact, draw, present] = frame.stages();
+
+            writeln!(
+                file,
+                "| {} | {:?} | {update:?} | {view:?} | {layout:?} | {interact:?} | {draw:?} | {present:?} |",
+                frame.index,
+                frame.total(),
+            )?;
+        }
+    }
+
+    writeln!(file)?;
+    writeln!(
+        file,
+        "_Generated by comet {} on {}._",
+        env!("CARGO_PKG_VERSION"),
+        format(SystemTime::now())
+    )?;
+
+    file.flush()
+}
+
+pub fn screenshot(screenshot: &window::Screenshot, path: &Path) -> io::Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+
+    let mut encoder = png::Encoder::new(file, screenshot.size.width, screenshot.size.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+
+    writer
+        .write_image_data(&screenshot.rgba)
+        .map_err(io::Error::other)
+}
+
+/// Writes the chart drawn by `draw` as an SVG image, under its title.
+pub fn chart(
+    path: &Path,
+    title: &str,
+    theme: &Theme,
+    draw: impl FnOnce(&mut Svg, Size),
+) -> io::Result<()> {
+    const WIDTH: f32 = 800.0;
+    const HEIGHT: f32 = 300.0;
+    const HEADER: f32 = 30.0;
+
+    let palette = theme.palette();
+    let mut svg = Svg::default();
+
+    draw(&mut svg, Size::new(WIDTH, HEIGHT - HEADER));
+
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(
+        file,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" \
+        viewBox=\"0 0 {WIDTH} {HEIGHT}\" font-family=\"monospace\">"
+    )?;
+
+    writeln!(
+        file,
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+        hex(palette.background.base.color)
+    )?;
+
+    writeln!(
+        file,
+        "<text x=\"10\" y=\"20\" font-size=\"14\" fill=\"{}\">{}</text>",
+        hex(palette.background.base.text),
+        escape(title)
+    )?;
+
+    writeln!(
+        file,
+        "<g transform=\"translate(0 {HEADER})\">\n{}</g>\n</svg>",
+        svg.body
+    )?;
+
+    file.flush()
+}
+
+/// An SVG image being drawn as a [`chart::Surface`].
+#[derive(Debug, Default)]
+pub struct Svg {
+    body: String,
+}
+
+impl chart::Surface for Svg {
+    fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color) {
+        let _ = writeln!(
+            self.body,
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" {}/>",
+            top_left.x,
+            top_left.y,
+            size.width,
+            size.height,
+            fill(color)
+        );
+    }
+
+    fn fill_triangle(&mut self, [a, b, c]: [Point; 3], color: Color) {
+        let _ = writeln!(
+            self.body,
+            "<polygon points=\"{:.2},{:.2} {:.2},{:.2} {:.2},{:.2}\" {}/>",
+            a.x,
+            a.y,
+            b.x,
+            b.y,
+            c.x,
+            c.y,
+            fill(color)
+        );
+    }
+
+    fn fill_text(&mut self, text: canvas::Text) {
+        let anchor = match text.align_x {
+            widget::text::Alignment::Center => "middle",
+            widget::text::Alignment::Right => "end",
+            _ => "start",
+        };
+
+        let baseline = match text.align_y {
+            alignment::Vertical::Top => "hanging",
+            alignment::Vertical::Center => "middle",
+            alignment::Vertical::Bottom => "auto",
+        };
+
+        let _ = writeln!(
+            self.body,
+            "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"{}\" text-anchor=\"{anchor}\" \
+            dominant-baseline=\"{baseline}\" {}>{}</text>",
+            text.position.x,
+            text.position.y,
+            text.size.0,
+            fill(text.color),
+            escape(&text.content)
+        );
+    }
+}
+
+pub fn stage(span: &Span) -> &'static str {
+    match span {
+        Span::Boot => "Boot",
+        Span::Update { .. } => "Update",
+        Span::View { .. } => "View",
+        Span::Layout { .. } => "Layout",
+        Span::Interact { .. } => "Interact",
+        Span::Draw { .. } => "Draw",
+        Span::Present { .. } => "Present",
+        Span::Custom { .. } => "Custom",
+    }
+}
+
+fn seconds(time: SystemTime) -> f64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+fn hex(color: Color) -> String {
+    let [r, g, b, _a] = color.into_rgba8();
+
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn fill(color: Color) -> String {
+    if color.a < 1.0 {
+        format!("fill=\"{}\" fill-opacity=\"{:.2}\"", hex(color), color.a)
+    } else {
+        format!("fill=\"{}\"", hex(color))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,76 @@
+/// A filter of update messages.
+///
+/// A filter is made of whitespace separated terms. A message matches
+/// when it contains any of the plain terms (or there are none) and none
+/// of the terms prefixed with `!`; so `!Tick` hides every `Tick` message
+/// and `Search Submit` shows only those two variants.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    raw: String,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl Filter {
+    pub fn new(raw: impl Into<String>) -> Self {
+        let raw = raw.into();
+
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+
+        for term in raw.split_whitespace() {
+            match term.strip_prefix('!') {
+                Some("") => {}
+                Some(term) => exclude.push(term.to_lowercase()),
+                None => include.push(term.to_lowercase()),
+            }
+        }
+
+        Self {
+            raw,
+            include,
+            exclude,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, message: &str) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let message = message.to_lowercase();
+
+        (self.include.is_empty() || self.include.iter().any(|term| message.contains(term)))
+            && !self.exclude.iter().any(|term| message.contains(term))
+    }
+}
+
+/// How update messages are filtered and trapped across screens.
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    /// The filter of the message log.
+    pub messages: Filter,
+    /// Whether the message rate chart only counts filtered messages.
+    pub message_rate: bool,
+    /// The filters that pause the playhead when a matching message is
+    /// received live.
+    pub breakpoints: Vec<Filter>,
+    /// Whether hitting a breakpoint also rewinds the application.
+    pub rewind: bool,
+}
+
+impl Settings {
+    pub fn breaks_on(&self, message: &str) -> bool {
+        self.breakpoints
+            .iter()
+            .any(|breakpoint| !breakpoint.is_empty() && breakpoint.matches(message))
+    }
+}
@@ -0,0 +1,248 @@
+//! End-to-end tests that run a real beacon server, drive it with a
+//! scripted client, and assert on the resulting [`Timeline`].
+use crate::beacon::{self, client, span};
+use crate::chart;
+use crate::core::window;
+use crate::timeline::{Playhead, Timeline};
+
+use iced::futures::StreamExt;
+use iced::time::Duration;
+
+use std::env;
+use std::net::TcpListener;
+use std::process::Command;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Marks the child process running a single harness test.
+const CHILD: &str = "COMET_HARNESS_CHILD";
+
+struct Harness {
+    client: beacon::Client,
+    events: mpsc::UnboundedReceiver<beacon::Event>,
+    timeline: Timeline,
+    server: JoinHandle<()>,
+}
+
+impl Harness {
+    /// Starts a harness for the given test, unless it is not running in its
+    /// own child process yet; in that case, the test is run in a new one and
+    /// `None` is returned.
+    ///
+    /// The beacon only reads its address from the environment, and changing
+    /// the environment while other tests run in parallel threads is unsound.
+    /// Instead, every harness test runs in a child process of the test binary
+    /// whose environment already holds a free address when it starts.
+    async fn start(test: &str, name: &'static str) -> Option<Self> {
+        if env::var_os(CHILD).is_none() {
+            run_isolated(test);
+            return None;
+        }
+
+        let (sender, events) = mpsc::unbounded_channel();
+
+        let server = tokio::spawn(async move {
+            let mut stream = std::pin::pin!(beacon::run());
+
+            while let Some(event) = stream.next().await {
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let client = client::connect(client::Metadata {
+            name,
+            theme: None,
+            can_time_travel: false,
+        });
+
+        Some(Self {
+            client,
+            events,
+            timeline: Timeline::new(),
+            server,
+        })
+    }
+
+    fn update(&self, number: usize, message: &str, tasks: usize, duration: Duration) {
+        self.client
+            .log(client::Event::SpanStarted(span::Stage::Update));
+        self.client.log(client::Event::MessageLogged {
+            number,
+            message: message.to_owned(),
+        });
+        self.client.log(client::Event::CommandsSpawned(tasks));
+        self.client.log(client::Event::SubscriptionsTracked(1));
+        self.client
+            .log(client::Event::SpanFinished(span::Stage::Update, duration));
+    }
+
+    fn view(&self, window: window::Id, duration: Duration) {
+        self.client
+            .log(client::Event::SpanStarted(span::Stage::View(window)));
+        self.client.log(client::Event::SpanFinished(
+            span::Stage::View(window),
+            duration,
+        ));
+    }
+
+    async fn wait_for(&mut self, amount: usize, is_expected: impl Fn(&beacon::Event) -> bool) {
+        let mut received = 0;
+
+        while received < amount {
+            let event = tokio::time::timeout(Duration::from_secs(5), self.events.recv())
+                .await
+                .expect("Receive event in time")
+                .expect("Server is running");
+
+            if let beacon::Event::AlreadyRunning { .. } = event {
+                panic!("Another process took the address of the beacon server");
+            }
+
+            if is_expected(&event) {
+                received += 1;
+            }
+
+            self.timeline.push(event);
+        }
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Runs the given test of this module in a child process of the test
+/// binary, listening on a free address, and asserts it passes.
+fn run_isolated(test: &str) {
+    let address = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("Find free port");
+
+    let module = module_path!()
+        .split_once("::")
+        .map_or(module_path!(), |(_, module)| module);
+
+    let status = Command::new(env::current_exe().expect("Find test binary"))
+        .args([&format!("{module}::{test}"), "--exact", "--nocapture"])
+        .env(CHILD, "1")
+        .env("ICED_BEACON_SERVER_ADDRESS", address.to_string())
+        .status()
+        .expect("Run test in child process");
+
+    assert!(status.success(), "{test} failed in its child process");
+}
+
+fn is_update(event: &beacon::Event) -> bool {
+    matches!(
+        event,
+        beacon::Event::SpanFinished {
+            span: span::Span::Update { .. },
+            ..
+        }
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_records_connection_and_updates() {
+    let Some(mut harness) = Harness::start("it_records_connection_and_updates", "harness").await
+    else {
+        return;
+    };
+
+    harness
+        .wait_for(
+            1,
+            |event| matches!(event, beacon::Event::Connected { name, .. } if name == "harness"),
+        )
+        .await;
+
+    harness.update(1, "Increment", 0, Duration::from_millis(1));
+    harness.update(2, "Increment", 2, Duration::from_millis(3));
+    harness.update(3, "Reset", 1, Duration::from_millis(2));
+
+    harness.wait_for(3, is_update).await;
+
+    let updates: Vec<_> = harness.timeline.updates(Playhead::Live).collect();
+
+    assert_eq!(updates.len(), 3);
+    assert_eq!(
+        updates
+            .iter()
+            .map(|update| &*update.message)
+            .collect::<Vec<_>>(),
+        ["Reset", "Increment", "Increment"]
+    );
+    assert_eq!(
+        updates
+            .iter()
+            .map(|update| update.tasks)
+            .collect::<Vec<_>>(),
+        [1, 2, 0]
+    );
+    assert_eq!(
+        updates
+            .iter()
+            .map(|update| update.number)
+            .collect::<Vec<_>>(),
+        [3, 2, 1]
+    );
+
+    let total: usize = harness
+        .timeline
+        .update_rate(Playhead::Live)
+        .map(|bucket| bucket.total)
+        .sum();
+
+    assert_eq!(total, 3);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_computes_stage_timeframes() {
+    let Some(mut harness) = Harness::start("it_computes_stage_timeframes", "harness").await else {
+        return;
+    };
+    let window = window::Id::unique();
+
+    harness
+        .wait_for(1, |event| matches!(event, beacon::Event::Connected { .. }))
+        .await;
+
+    for millis in [2, 4, 6] {
+        harness.view(window, Duration::from_millis(millis));
+    }
+
+    harness
+        .wait_for(3, |event| chart::Stage::View.duration(event).is_some())
+        .await;
+
+    let durations: Vec<_> = harness
+        .timeline
+        .timeframes(Playhead::Live, chart::Stage::View.span(), |event| {
+            chart::Stage::View.duration(event)
+        })
+        .map(|timeframe| timeframe.duration)
+        .collect();
+
+    assert_eq!(
+        durations,
+        [2, 4, 6]
+            .into_iter()
+            .rev()
+            .map(Duration::from_millis)
+            .collect::<Vec<_>>()
+    );
+
+    assert!(
+        harness
+            .timeline
+            .timeframes(Playhead::Live, chart::Stage::Layout.span(), |event| {
+                chart::Stage::Layout.duration(event)
+            })
+            .next()
+            .is_none()
+    );
+}
@@ -0,0 +1,499 @@
+use iced::keyboard;
+use iced::keyboard::key::Named;
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// Something comet can do with a keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    ToggleHelp,
+    TogglePause,
+    PreviousUpdate,
+    NextUpdate,
+    Previous(usize),
+    Next(usize),
+    First,
+    GoLive,
+    ShowOverview,
+    ShowUpdate,
+    ShowPresent,
+    ShowCustom,
+    ShowFlamegraph,
+    ShowOffenders,
+    ShowStats,
+    ShowBoot,
+    ShowPalette,
+    ShowMessages,
+    ShowCompare,
+    ShowBoard,
+    ToggleMaximized,
+    SwitchBoard(usize),
+    SaveSession,
+    GenerateReport,
+    ToggleHistogram,
+    CycleBudget,
+    CycleTail,
+    ClearTimeline,
+    ToggleFreeze,
+    ToggleAxis,
+    ToggleAnomalies,
+    PreviousAnomaly,
+    NextAnomaly,
+    TogglePlayback,
+    ToggleBookmark,
+    PreviousBookmark,
+    NextBookmark,
+    IncrementBuckets,
+    DecrementBuckets,
+    IncrementBarWidth,
+    DecrementBarWidth,
+}
+
+/// The actions that can be bound, with their name in the configuration
+/// file, their default binding, and a description for the help overlay.
+const ACTIONS: &[(Action, &str, &str, &str)] = &[
+    (Action::Quit, "quit", "F12", "Quit"),
+    (
+        Action::ToggleHelp,
+        "toggle_help",
+        "?",
+        "Show or hide this help",
+    ),
+    (
+        Action::TogglePause,
+        "toggle_pause",
+        "Space",
+        "Pause or go live",
+    ),
+    (
+        Action::PreviousUpdate,
+        "previous_update",
+        "Ctrl+Left",
+        "Previous update",
+    ),
+    (
+        Action::NextUpdate,
+        "next_update",
+        "Ctrl+Right",
+        "Next update",
+    ),
+    (Action::Previous(1), "previous", "Left", "Previous event"),
+    (Action::Next(1), "next", "Right", "Next event"),
+    (
+        Action::Previous(10),
+        "previous_10",
+        "Shift+Left",
+        "Back 10 events",
+    ),
+    (
+        Action::Next(10),
+        "next_10",
+        "Shift+Right",
+        "Forward 10 events",
+    ),
+    (
+        Action::Previous(100),
+        "previous_100",
+        "PageUp",
+        "Back 100 events",
+    ),
+    (
+        Action::Next(100),
+        "next_100",
+        "PageDown",
+        "Forward 100 events",
+    ),
+    (Action::First, "first", "Home", "First event"),
+    (Action::GoLive, "go_live", "End", "Go live"),
+    (Action::ShowOverview, "show_overview", "o", "Overview"),
+    (Action::ShowUpdate, "show_update", "u", "Update"),
+    (Action::ShowPresent, "show_present", "p", "Present"),
+    (Action::ShowCustom, "show_custom", "c", "Custom"),
+    (Action::ShowFlamegraph, "show_flamegraph", "f", "Flamegraph"),
+    (Action::ShowOffenders, "show_offenders", "w", "Offenders"),
+    (Action::ShowStats, "show_stats", "n", "Stats"),
+    (Action::ShowBoot, "show_boot", "i", "Boot"),
+    (Action::ShowPalette, "show_palette", "l", "Palette"),
+    (Action::ShowMessages, "show_messages", "m", "Messages"),
+    (Action::ShowCompare, "show_compare", "v", "Compare"),
+    (Action::ShowBoard, "show_board", "d", "Board"),
+    (
+        Action::ToggleMaximized,
+        "toggle_maximized",
+        "Shift+M",
+        "Maximize or restore pane",
+    ),
+    (
+        Action::SwitchBoard(0),
+        "switch_board_1",
+        "1",
+        "Switch to board 1",
+    ),
+    (
+        Action::SwitchBoard(1),
+        "switch_board_2",
+        "2",
+        "Switch to board 2",
+    ),
+    (
+        Action::SwitchBoard(2),
+        "switch_board_3",
+        "3",
+        "Switch to board 3",
+    ),
+    (
+        Action::SwitchBoard(3),
+        "switch_board_4",
+        "4",
+        "Switch to board 4",
+    ),
+    (
+        Action::SwitchBoard(4),
+        "switch_board_5",
+        "5",
+        "Switch to board 5",
+    ),
+    (
+        Action::SwitchBoard(5),
+        "switch_board_6",
+        "6",
+        "Switch to board 6",
+    ),
+    (
+        Action::SwitchBoard(6),
+        "switch_board_7",
+        "7",
+        "Switch to board 7",
+    ),
+    (
+        Action::SwitchBoard(7),
+        "switch_board_8",
+        "8",
+        "Switch to board 8",
+    ),
+    (
+        Action::SwitchBoard(8),
+        "switch_board_9",
+        "9",
+        "Switch to board 9",
+    ),
+    (Action::SaveSession, "save_session", "s", "Save session"),
+    (
+        Action::GenerateReport,
+        "generate_report",
+        "r",
+        "Generate report",
+    ),
+    (
+        Action::ToggleHistogram,
+        "toggle_histogram",
+        "h",
+        "Toggle histogram",
+    ),
+    (
+        Action::CycleBudget,
+        "cycle_budget",
+        "g",
+        "Cycle frame budget",
+    ),
+    (Action::CycleTail, "cycle_tail", "e", "Cycle tail length"),
+    (
+        Action::ClearTimeline,
+        "clear_timeline",
+        "x",
+        "Clear the timeline",
+    ),
+    (
+        Action::ToggleFreeze,
+        "toggle_freeze",
+        "z",
+        "Stop or resume recording",
+    ),
+    (Action::ToggleAxis, "toggle_axis", "t", "Toggle time axis"),
+    (
+        Action::ToggleAnomalies,
+        "toggle_anomalies",
+        "a",
+        "Toggle anomalies",
+    ),
+    (
+        Action::PreviousAnomaly,
+        "previous_anomaly",
+        ",",
+        "Previous anomaly",
+    ),
+    (Action::NextAnomaly, "next_anomaly", ".", "Next anomaly"),
+    (
+        Action::TogglePlayback,
+        "toggle_playback",
+        "k",
+        "Start or stop playback",
+    ),
+    (
+        Action::ToggleBookmark,
+        "toggle_bookmark",
+        "b",
+        "Toggle bookmark",
+    ),
+    (
+        Action::PreviousBookmark,
+        "previous_bookmark",
+        "{",
+        "Previous bookmark",
+    ),
+    (Action::NextBookmark, "next_bookmark", "}", "Next bookmark"),
+    (
+        Action::IncrementBuckets,
+        "increment_buckets",
+        "]",
+        "More histogram buckets",
+    ),
+    (
+        Action::DecrementBuckets,
+        "decrement_buckets",
+        "[",
+        "Fewer histogram buckets",
+    ),
+    (
+        Action::IncrementBarWidth,
+        "increment_bar_width",
+        "Up",
+        "Wider bars",
+    ),
+    (
+        Action::DecrementBarWidth,
+        "decrement_bar_width",
+        "Down",
+        "Narrower bars",
+    ),
+];
+
+/// The keyboard shortcuts of comet.
+///
+/// Every action has a default binding, which can be changed or removed
+/// (with an empty string) by name in the `[keys]` table of the settings.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Option<Binding>)>,
+}
+
+impl Keymap {
+    pub fn new(overrides: &BTreeMap<String, String>) -> Self {
+        for name in overrides.keys() {
+            if !ACTIONS.iter().any(|(_, candidate, _, _)| candidate == name) {
+                log::warn!("Unknown keyboard action: {name}");
+            }
+        }
+
+        let bindings = ACTIONS
+            .iter()
+            .map(|(action, name, default, _)| {
+                let binding = match overrides.get(*name) {
+                    Some(spec) if spec.trim().is_empty() => None,
+                    Some(spec) => match spec.parse() {
+                        Ok(binding) => Some(binding),
+                        Err(error) => {
+                            log::warn!("Invalid binding for {name}: {error}");
+                            default.parse().ok()
+                        }
+                    },
+                    None => default.parse().ok(),
+                };
+
+                (*action, binding)
+            })
+            .collect();
+
+        Self { bindings }
+    }
+
+    /// Returns the action bound to the given key press, if any.
+    pub fn action(&self, key: &keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Action> {
+        self.bindings.iter().find_map(|(action, binding)| {
+            binding
+                .as_ref()
+                .filter(|binding| binding.matches(key, modifiers))
+                .map(|_| *action)
+        })
+    }
+
+    /// Returns the binding of the given action, if it has one.
+    pub fn binding(&self, action: Action) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .find(|(candidate, _)| *candidate == action)
+            .and_then(|(_, binding)| binding.as_ref())
+    }
+
+    /// Appends the binding of the given action to a tooltip, if it has one.
+    pub fn hint(&self, tip: &str, action: Action) -> String {
+        match self.binding(action) {
+            Some(binding) => format!("{tip} ({binding})"),
+            None => tip.to_owned(),
+        }
+    }
+
+    /// Returns the description and binding of every bound action.
+    pub fn help(&self) -> impl Iterator<Item = (&'static str, &Binding)> {
+        self.bindings
+            .iter()
+            .zip(ACTIONS)
+            .filter_map(|((_, binding), (_, _, _, description))| {
+                Some((*description, binding.as_ref()?))
+            })
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new())
+    }
+}
+
+/// A key combined with some modifiers, like `Ctrl+Left` or `M`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    key: keyboard::Key,
+    command: bool,
+    alt: bool,
+    shift: bool,
+}
+
+impl Binding {
+    /// Whether the binding matches a key press.
+    ///
+    /// Shift is ignored for characters, since it is already reflected by
+    /// the character itself (e.g. `M` or `{`).
+    pub fn matches(&self, key: &keyboard::Key, modifiers: keyboard::Modifiers) -> bool {
+        self.key == *key
+            && self.command == modifiers.command()
+            && self.alt == modifiers.alt()
+            && (matches!(self.key, keyboard::Key::Character(_)) || self.shift == modifiers.shift())
+    }
+}
+
+const NAMED: &[(&str, Named)] = &[
+    ("Space", Named::Space),
+    ("Left", Named::ArrowLeft),
+    ("Right", Named::ArrowRight),
+    ("Up", Named::ArrowUp),
+    ("Down", Named::ArrowDown),
+    ("PageUp", Named::PageUp),
+    ("PageDown", Named::PageDown),
+    ("Home", Named::Home),
+    ("End", Named::End),
+    ("Insert", Named::Insert),
+    ("Delete", Named::Delete),
+    ("Backspace", Named::Backspace),
+    ("Enter", Named::Enter),
+    ("Tab", Named::Tab),
+    ("Escape", Named::Escape),
+    ("F1", Named::F1),
+    ("F2", Named::F2),
+    ("F3", Named::F3),
+    ("F4", Named::F4),
+    ("F5", Named::F5),
+    ("F6", Named::F6),
+    ("F7", Named::F7),
+    ("F8", Named::F8),
+    ("F9", Named::F9),
+    ("F10", Named::F10),
+    ("F11", Named::F11),
+    ("F12", Named::F12),
+];
+
+impl std::str::FromStr for Binding {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let spec = spec.trim();
+
+        // The last `+` separates the key, unless the key is `+` itself
+        let (modifiers, key) = match spec.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => match spec.rsplit_once('+') {
+                Some((modifiers, key)) if !key.is_empty() => (modifiers, key),
+                _ if spec == "+" => ("", "+"),
+                Some(_) => return Err(format!("missing key in {spec:?}")),
+                None => ("", spec),
+            },
+        };
+
+        let mut binding = Self {
+            key: keyboard::Key::Unidentified,
+            command: false,
+            alt: false,
+            shift: false,
+        };
+
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" | "cmd" | "command" => binding.command = true,
+                "alt" | "option" => binding.alt = true,
+                "shift" => binding.shift = true,
+                _ => return Err(format!("unknown modifier {modifier:?}")),
+            }
+        }
+
+        binding.key = if let Some((_, named)) = NAMED
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        {
+            keyboard::Key::Named(*named)
+        } else if key.chars().count() == 1 {
+            if binding.shift {
+                keyboard::Key::Character(key.to_uppercase().into())
+            } else {
+                keyboard::Key::Character(key.into())
+            }
+        } else {
+            return Err(format!("unknown key {key:?}"));
+        };
+
+        Ok(binding)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.command {
+            f.write_str(if cfg!(target_os = "macos") {
+                "Cmd+"
+            } else {
+                "Ctrl+"
+            })?;
+        }
+
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+
+        match &self.key {
+            keyboard::Key::Named(named) => {
+                if self.shift {
+                    f.write_str("Shift+")?;
+                }
+
+                let name = NAMED
+                    .iter()
+                    .find(|(_, candidate)| candidate == named)
+                    .map_or("?", |(name, _)| name);
+
+                f.write_str(name)
+            }
+            keyboard::Key::Character(c) => {
+                if c.chars().all(char::is_uppercase) {
+                    write!(f, "Shift+{c}")
+                } else {
+                    f.write_str(&c.to_uppercase())
+                }
+            }
+            keyboard::Key::Unidentified => f.write_str("?"),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn bindings_can_be_overridden() {
+    let overrides = BTreeMap::from([
+        (String::from("quit"), String::from("Ctrl+Q")),
+        (String::from("show_overview"), String::new()),
+        (String::from("show_update"), String::from("Ctrl+Shift+Alt")),
+    ]);
+
+    let keymap = Keymap::new(&overrides);
+    let q = keyboard::Key::Character("q".into());
+    let o = keyboard::Key::Character("o".into());
+    let u = keyboard::Key::Character("u".into());
+    let f12 = keyboard::Key::Named(Named::F12);
+
+    assert_eq!(
+        keymap.action(&q, keyboard::Modifiers::COMMAND),
+        Some(Action::Quit)
+    );
+    assert_eq!(keymap.action(&q, keyboard::Modifiers::empty()), None);
+    assert_eq!(keymap.action(&f12, keyboard::Modifiers::empty()), None);
+    assert_eq!(keymap.action(&o, keyboard::Modifiers::empty()), None);
+    assert_eq!(
+        keymap.action(&u, keyboard::Modifiers::empty()),
+        Some(Action::ShowUpdate)
+    );
+}
+
+#[test]
+fn shift_only_matters_for_named_keys() {
+    let keymap = Keymap::default();
+    let left = keyboard::Key::Named(Named::ArrowLeft);
+    let brace = keyboard::Key::Character("{".into());
+
+    assert_eq!(
+        keymap.action(&left, keyboard::Modifiers::empty()),
+        Some(Action::Previous(1))
+    );
+    assert_eq!(
+        keymap.action(&left, keyboard::Modifiers::SHIFT),
+        Some(Action::Previous(10))
+    );
+    assert_eq!(
+        keymap.action(&brace, keyboard::Modifiers::SHIFT),
+        Some(Action::PreviousBookmark)
+    );
+}
+
+#[test]
+fn bindings_display_like_tooltips() {
+    let display = |spec: &str| spec.parse::<Binding>().expect("valid binding").to_string();
+
+    assert_eq!(display("s"), "S");
+    assert_eq!(display("Shift+m"), "Shift+M");
+    assert_eq!(display("pageup"), "PageUp");
+    assert_eq!(display("Alt++"), "Alt++");
+}
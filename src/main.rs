@@ -1,61 +1,177 @@
 use iced_beacon as beacon;
 use iced_beacon::core;
 
+mod appearance;
 mod chart;
+mod check;
+mod cli;
+mod config;
+mod control;
+mod daemon;
+mod export;
+mod filter;
 mod icon;
+mod keymap;
+mod otlp;
+mod playback;
 mod screen;
+mod session;
+mod stats;
+mod stress;
+mod summary;
 mod timeline;
 mod widget;
 
+#[cfg(test)]
+mod harness;
+
+use crate::appearance::{self, Appearance};
+use crate::keymap::{self, Keymap};
+use crate::playback::Playback;
 use crate::screen::Screen;
+use crate::screen::board;
+use crate::screen::compare;
 use crate::screen::custom;
+use crate::screen::messages;
+use crate::screen::offenders;
+use crate::screen::update;
 use crate::timeline::Timeline;
-use crate::widget::{circle, diffused_text, tip};
+use crate::widget::{card, card_with_action, circle, diffused_text, hover, markers, minimap, tip};
 
 use iced::border;
+use iced::futures::{Stream, StreamExt};
 use iced::keyboard;
-use iced::time::SystemTime;
+use iced::padding;
+use iced::system;
+use iced::theme;
+use iced::time::{self, Instant, SystemTime};
 use iced::widget::{
-    bottom, button, center, column, container, progress_bar, row, rule, slider, space, stack, svg,
-    text, tooltip,
+    bottom, button, center, column, container, opaque, pick_list, progress_bar, row, rule,
+    scrollable, slider, space, stack, svg, text, text_input, toggler, tooltip,
 };
 use iced::window;
 use iced::{Center, Element, Fill, Font, Point, Shrink, Size, Subscription, Task, Theme};
 
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
 pub fn main() -> iced::Result {
-    tracing_subscriber::fmt::init();
+    // Logs go to stderr so stdout stays parseable in headless modes
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    let options = match cli::parse() {
+        Ok(cli::Command::Gui(options)) => options,
+        Ok(cli::Command::Daemon { output }) => {
+            if let Err(error) = daemon::run(&output) {
+                log::error!("{error}");
+                std::process::exit(1);
+            }
+
+            return Ok(());
+        }
+        Ok(cli::Command::Stress {
+            events_per_second,
+            duration,
+        }) => {
+            stress::run(events_per_second, duration);
+
+            return Ok(());
+        }
+        Ok(cli::Command::Export {
+            session,
+            format,
+            output,
+        }) => {
+            if let Err(error) = export::session(&session, format, &output) {
+                log::error!("Failed to export {}: {error}", session.display());
+                std::process::exit(1);
+            }
+
+            log::info!("Exported {} to {}", session.display(), output.display());
+
+            return Ok(());
+        }
+        Ok(cli::Command::Check {
+            source,
+            thresholds,
+            baseline,
+        }) => match check::run(&source, &thresholds, baseline.as_ref()) {
+            Ok(true) => return Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(error) => {
+                log::error!("{error}");
+                std::process::exit(2);
+            }
+        },
+        Err(error) => {
+            log::error!("{error}");
+            std::process::exit(2);
+        }
+    };
 
-    if beacon::is_running() {
+    if !options.is_offline() && beacon::is_running() {
         log::warn!("comet is already running. Exiting...");
         std::process::exit(0);
     }
 
-    iced::application(Comet::new, Comet::update, Comet::view)
-        .title(Comet::title)
-        .subscription(Comet::subscription)
-        .theme(Comet::theme)
-        .font(icon::FONT)
-        .default_font(Font::MONOSPACE)
-        .window(window::Settings {
-            size: Size::new(800.0, 600.0),
-            position: window::Position::SpecificWith(|window, monitor| {
-                Point::new(monitor.width - window.width - 5.0, 0.0)
-            }),
-            ..window::Settings::default()
-        })
-        .run()
+    iced::daemon(
+        move || Comet::new(options.clone()),
+        Comet::update,
+        Comet::view,
+    )
+    .title(Comet::title)
+    .subscription(Comet::subscription)
+    .theme(Comet::theme)
+    .font(icon::FONT)
+    .default_font(Font::MONOSPACE)
+    .run()
 }
 
 #[derive(Debug)]
 struct Comet {
     logo: svg::Handle,
     state: State,
-    theme: Theme,
+    appearance: Appearance,
     timeline: Timeline,
     offset: timeline::Playhead,
     selection: timeline::Playhead,
+    pinned: Option<timeline::Index>,
     screen: Screen,
-    zoom: chart::Zoom,
+    chart: chart::Settings,
+    options: cli::Options,
+    recording: Option<session::Recording>,
+    recorder: Option<session::Recorder>,
+    exporter: Option<otlp::Exporter>,
+    baseline: chart::Baseline,
+    comparison: Option<compare::Comparison>,
+    filter: filter::Settings,
+    bookmarks: BTreeMap<timeline::Index, String>,
+    playback: Option<Playback>,
+    speed: playback::Speed,
+    hovered: Option<timeline::Index>,
+    /// The latest events received while ingestion is frozen, to be pushed
+    /// to the timeline once it resumes.
+    frozen: Option<Backlog>,
+    /// Whether the window is minimized, in which case incoming events are
+    /// only ingested and the screen is invalidated once it shows up again.
+    is_hidden: bool,
+    boards: board::Boards,
+    /// When the boards were last changed without being saved; they are
+    /// saved once they have been left alone for [`BOARDS_DELAY`].
+    boards_changed_at: Option<Instant>,
+    /// The persisted preferences, kept up to date with the geometry of the
+    /// main window.
+    settings: config::Settings,
+    /// The main window of comet.
+    window: window::Id,
+    /// The board modules popped out into their own windows.
+    popouts: BTreeMap<window::Id, board::Pane>,
+    keymap: Keymap,
+    is_help_open: bool,
 }
 
 #[derive(Debug)]
@@ -78,123 +194,529 @@ enum Connection {
     Disconnected {
         at: SystemTime,
     },
+    Session {
+        path: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone)]
 enum Message {
-    EventReported(beacon::Event),
+    EventsReported(Vec<beacon::Event>),
+    ControlReceived(control::Command),
+    ScreenshotTaken(PathBuf, window::Screenshot),
+    AutoSaveElapsed,
+    BoardsSettled(Instant),
+    SessionRead(Vec<session::Entry>),
+    SaveSession,
+    GenerateReport,
+    OpenSession(PathBuf),
+    WindowUnfocused(window::Id),
+    WindowClosed(window::Id),
+    WindowMoved(window::Id, Point),
+    WindowResized(window::Id, Size),
+    VisibilityChanged(window::Id, bool),
+    SessionLoaded(PathBuf, Result<Vec<session::Entry>, String>),
+    BaselineLoaded(PathBuf, Result<Vec<session::Entry>, String>),
+    ComparisonLoaded(
+        (PathBuf, Result<Vec<session::Entry>, String>),
+        (PathBuf, Result<Vec<session::Entry>, String>),
+    ),
     PlayheadChanged(timeline::Index),
+    PlayheadHovered(Option<timeline::Index>),
+    RunSelected(timeline::Run),
+    WindowsSelected(chart::Windows),
     TogglePause,
-    Previous,
-    Next,
+    Previous(usize),
+    Next(usize),
+    First,
+    PreviousUpdate,
+    NextUpdate,
+    TogglePlayback,
+    PlaybackSpeedChanged(playback::Speed),
+    PlaybackTicked(Instant),
     GoLive,
     ShowOverview,
     ShowUpdate,
     ShowPresent,
     ShowCustom,
+    ShowFlamegraph,
+    ShowOffenders,
+    ShowStats,
+    ShowBoot,
+    ShowPalette,
+    ShowMessages,
+    ShowCompare,
+    ShowBoard,
+    SwitchBoard(usize),
+    ToggleMaximized,
     Custom(custom::Message),
+    Update(update::Message),
+    Board(board::Message),
+    Offenders(offenders::Message),
+    Messages(messages::Message),
     Chart(chart::Interaction),
+    Compare(chart::Interaction),
     IncrementBarWidth,
     DecrementBarWidth,
+    ToggleHistogram,
+    CycleBudget,
+    CycleTail,
+    ClearTimeline,
+    ToggleFreeze,
+    ToggleAxis,
+    ToggleAnomalies,
+    PreviousAnomaly,
+    NextAnomaly,
+    ToggleBookmark,
+    PreviousBookmark,
+    NextBookmark,
+    MarkerSelected(timeline::Index),
+    BookmarkRenamed(timeline::Index, String),
+    IncrementBuckets,
+    DecrementBuckets,
+    ThemeSelected(appearance::Choice),
+    SystemThemeChanged(theme::Mode),
+    FollowAppToggled(bool),
+    KeyPressed(keyboard::Key, keyboard::Modifiers),
+    ToggleHelp,
     Quit,
 }
 
 impl Comet {
-    fn new() -> (Self, Task<Message>) {
+    fn new(options: cli::Options) -> (Self, Task<Message>) {
+        let open = options
+            .open
+            .clone()
+            .map(|path| Task::done(Message::OpenSession(path)))
+            .unwrap_or_else(Task::none);
+
+        let recorder =
+            options
+                .record
+                .as_deref()
+                .and_then(|path| match session::Recorder::append(path) {
+                    Ok(recorder) => {
+                        log::info!("Recording to {}", path.display());
+                        Some(recorder)
+                    }
+                    Err(error) => {
+                        log::error!("Failed to record to {}: {error}", path.display());
+                        None
+                    }
+                });
+
+        let exporter = options.otlp.clone().map(otlp::Exporter::new);
+
+        let mut timeline = Timeline::new();
+        timeline.set_retention(options.retention);
+
+        let settings: config::Settings = config::load(SETTINGS).unwrap_or_default();
+
+        let (window, open_window) = window::open(match settings.window {
+            Some(geometry) => window::Settings {
+                size: Size::new(geometry.width, geometry.height),
+                position: window::Position::Specific(Point::new(geometry.x, geometry.y)),
+                ..window::Settings::default()
+            },
+            None => window::Settings {
+                size: Size::new(800.0, 600.0),
+                position: window::Position::SpecificWith(|window, monitor| {
+                    Point::new(monitor.width - window.width - 5.0, 0.0)
+                }),
+                ..window::Settings::default()
+            },
+        });
+
+        let theme = settings
+            .theme
+            .as_deref()
+            .and_then(appearance::Choice::from_name)
+            .unwrap_or(appearance::Choice::Builtin(Theme::CatppuccinMocha));
+
+        let appearance = Appearance::new(theme, settings.follow_app.unwrap_or(true));
+
+        let mut boards: board::Boards = config::load(BOARDS)
+            .filter(board::Boards::is_valid)
+            .unwrap_or_default();
+
+        let _ = boards.select(settings.board);
+
+        let keymap = Keymap::new(&settings.keys);
+
+        let baseline = options
+            .baseline
+            .clone()
+            .map(|path| {
+                Task::perform(session::load(path.clone()), move |result| {
+                    Message::BaselineLoaded(path.clone(), result)
+                })
+            })
+            .unwrap_or_else(Task::none);
+
+        let compare = options
+            .compare
+            .clone()
+            .map(|(before, after)| {
+                Task::perform(
+                    async move {
+                        let before_entries = session::load(before.clone()).await;
+                        let after_entries = session::load(after.clone()).await;
+
+                        ((before, before_entries), (after, after_entries))
+                    },
+                    |(before, after)| Message::ComparisonLoaded(before, after),
+                )
+            })
+            .unwrap_or_else(Task::none);
+
         (
             Self {
                 logo: svg::Handle::from_memory(include_bytes!("../assets/logo.svg")),
                 state: State::Waiting,
-                theme: Theme::CatppuccinMocha,
-                timeline: Timeline::new(),
+                appearance,
+                timeline,
                 offset: timeline::Playhead::Live,
                 selection: timeline::Playhead::Live,
+                pinned: None,
                 screen: Screen::Overview(screen::Overview::new()),
-                zoom: chart::Zoom::default(),
+                chart: chart::Settings {
+                    zoom: settings.zoom.unwrap_or_default(),
+                    budget: options
+                        .frame_budget
+                        .or(settings.frame_budget.and_then(|milliseconds| {
+                            time::Duration::try_from_secs_f64(milliseconds / 1_000.0).ok()
+                        }))
+                        .map(chart::Budget::new),
+                    tail: options.tail.map(chart::Tail::new),
+                    ..chart::Settings::default()
+                },
+                options,
+                recording: None,
+                recorder,
+                exporter,
+                baseline: chart::Baseline::default(),
+                comparison: None,
+                filter: filter::Settings::default(),
+                bookmarks: BTreeMap::new(),
+                playback: None,
+                speed: playback::Speed::default(),
+                hovered: None,
+                frozen: None,
+                is_hidden: false,
+                boards,
+                boards_changed_at: None,
+                settings,
+                window,
+                popouts: BTreeMap::new(),
+                keymap,
+                is_help_open: false,
             },
-            Task::none(),
+            Task::batch([
+                open_window.discard(),
+                system::theme().map(Message::SystemThemeChanged),
+                open,
+                baseline,
+                compare,
+            ]),
         )
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::EventReported(event) => {
-                match event.clone() {
-                    beacon::Event::Connected {
-                        connection,
-                        name,
-                        version,
-                        theme,
-                        can_time_travel,
-                        ..
-                    } => {
-                        let current_name = match &self.state {
-                            State::Working { name, .. } => Some(name),
-                            State::Waiting => None,
-                        };
+            Message::EventsReported(events) => {
+                let task = Task::batch(events.into_iter().map(|event| self.report(event)));
+                self.flush_recorder();
 
-                        if Some(&name) != current_name {
-                            self.offset = timeline::Playhead::Live;
-                            self.selection = timeline::Playhead::Live;
-                            self.timeline.clear();
-                        }
+                task
+            }
+            Message::ControlReceived(command) => match command {
+                control::Command::Pause => {
+                    if self.offset.is_live() {
+                        self.update_playhead(timeline::Playhead::Paused(self.timeline.end()))
+                    } else {
+                        Task::none()
+                    }
+                }
+                control::Command::Live => self.update_playhead(timeline::Playhead::Live),
+                control::Command::Seek(offset) => {
+                    let Some(start) = self.timeline.start() else {
+                        return Task::none();
+                    };
 
-                        if let Some(palette) = theme {
-                            self.theme = Theme::custom(name.clone(), palette);
-                        }
+                    let Some(time) = start.checked_add(offset) else {
+                        log::warn!("Cannot seek {offset:?} past the start of the timeline");
+                        return Task::none();
+                    };
 
-                        self.state = State::Working {
-                            name,
-                            can_time_travel,
-                            connection: Connection::Connected {
-                                client: connection,
-                                version,
-                            },
-                        };
+                    self.update_playhead(timeline::Playhead::Paused(self.timeline.index_at(time)))
+                }
+                control::Command::Export(control::Format::Csv, path) => {
+                    if let Err(error) = export::csv(&self.timeline, &path) {
+                        log::error!("Failed to export {}: {error}", path.display());
                     }
-                    beacon::Event::Disconnected { at } => {
-                        if let State::Working { connection, .. } = &mut self.state {
-                            *connection = Connection::Disconnected { at };
-                        }
+
+                    Task::none()
+                }
+                control::Command::Screenshot(path) => window::screenshot(self.window)
+                    .map(move |screenshot| Message::ScreenshotTaken(path.clone(), screenshot)),
+            },
+            Message::ScreenshotTaken(path, screenshot) => {
+                if let Err(error) = export::screenshot(&screenshot, &path) {
+                    log::error!("Failed to save screenshot {}: {error}", path.display());
+                }
+
+                Task::none()
+            }
+            Message::BoardsSettled(now) => {
+                if self
+                    .boards_changed_at
+                    .is_some_and(|changed_at| now.duration_since(changed_at) >= BOARDS_DELAY)
+                {
+                    self.save_boards();
+                }
+
+                Task::none()
+            }
+            Message::AutoSaveElapsed => {
+                self.save_recording();
+
+                if let Some(recording) = &mut self.recording {
+                    recording.restart(self.timeline.end());
+                }
+
+                Task::none()
+            }
+            Message::SessionRead(entries) => {
+                let Some(path) = self.options.attach.clone().or(self.options.replay.clone()) else {
+                    return Task::none();
+                };
+
+                for entry in entries {
+                    self.replay(entry, &path);
+                }
+
+                Task::none()
+            }
+            Message::SaveSession => {
+                let State::Working { name, .. } = &self.state else {
+                    return Task::none();
+                };
+
+                let directory = self.options.auto_save.clone().unwrap_or_default();
+                let path = session::path(&directory, name, SystemTime::now());
+
+                self.save_session(&path, *self.timeline.range().start());
+
+                Task::none()
+            }
+            Message::GenerateReport => {
+                let State::Working {
+                    name, connection, ..
+                } = &self.state
+                else {
+                    return Task::none();
+                };
+
+                let version = match connection {
+                    Connection::Connected { version, .. } => Some(version.to_string()),
+                    Connection::Disconnected { .. } | Connection::Session { .. } => None,
+                };
+
+                let directory = self.options.auto_save.clone().unwrap_or_default();
+                let path = session::path(&directory, name, SystemTime::now()).with_extension("md");
+
+                let result = path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| export::report(&path, name, version.as_deref(), &self.timeline));
+
+                match result {
+                    Ok(()) => log::info!("Report generated at {}", path.display()),
+                    Err(error) => {
+                        log::error!("Failed to generate report {}: {error}", path.display());
                     }
-                    beacon::Event::ThemeChanged { seed, .. } => {
-                        if let State::Working { name, .. } = &self.state {
-                            self.theme = Theme::custom(name.clone(), seed);
-                        }
+                }
+
+                Task::none()
+            }
+            Message::OpenSession(path) => {
+                Task::perform(session::load(path.clone()), move |result| {
+                    Message::SessionLoaded(path.clone(), result)
+                })
+            }
+            Message::SessionLoaded(path, result) => {
+                let entries = match result {
+                    Ok(entries) => entries,
+                    Err(error) => {
+                        log::error!("Failed to open session {}: {error}", path.display());
+                        return Task::none();
+                    }
+                };
+
+                self.clear();
+                self.state = State::Waiting;
+                self.timeline.set_retention(self.options.retention);
+                self.recording = None;
+
+                for entry in entries {
+                    self.replay(entry, &path);
+                }
+
+                // The connection may have been evicted before the session was saved
+                if let State::Waiting = self.state {
+                    self.state = State::Working {
+                        name: path
+                            .file_stem()
+                            .map(|name| name.to_string_lossy().into_owned())
+                            .unwrap_or_default(),
+                        can_time_travel: false,
+                        connection: Connection::Session { path },
+                    };
+                }
+
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::BaselineLoaded(path, result) => {
+                match result {
+                    Ok(entries) => {
+                        log::info!("Comparing against baseline {}", path.display());
+
+                        self.baseline = chart::Baseline::new(entries);
+                        self.invalidate();
                     }
-                    beacon::Event::SpanFinished { .. } => {}
-                    beacon::Event::QuitRequested { .. } | beacon::Event::AlreadyRunning { .. } => {
-                        return iced::exit();
+                    Err(error) => {
+                        log::error!("Failed to open baseline {}: {error}", path.display());
                     }
                 }
 
-                self.screen.invalidate_by(&event);
-                self.timeline.push(event);
+                Task::none()
+            }
+            Message::ComparisonLoaded((before, before_entries), (after, after_entries)) => {
+                let (before_entries, after_entries) = match (before_entries, after_entries) {
+                    (Ok(before_entries), Ok(after_entries)) => (before_entries, after_entries),
+                    (Err(error), _) => {
+                        log::error!("Failed to open session {}: {error}", before.display());
+                        return Task::none();
+                    }
+                    (_, Err(error)) => {
+                        log::error!("Failed to open session {}: {error}", after.display());
+                        return Task::none();
+                    }
+                };
+
+                let comparison =
+                    compare::Comparison::new((&before, before_entries), (&after, after_entries));
+
+                self.state = State::Working {
+                    name: comparison.name(),
+                    can_time_travel: false,
+                    connection: Connection::Session { path: after },
+                };
+                self.comparison = Some(comparison);
+                self.screen = Screen::Compare(screen::Compare::new());
 
                 Task::none()
             }
             Message::PlayheadChanged(index) => {
                 self.update_playhead(timeline::Playhead::Paused(index))
             }
+            Message::PlayheadHovered(index) => {
+                self.hovered = index;
+
+                Task::none()
+            }
+            Message::RunSelected(run) => self.update_playhead(self.timeline.end_of(&run)),
+            Message::WindowsSelected(windows) => {
+                self.chart.windows = windows;
+                self.invalidate();
+
+                Task::none()
+            }
             Message::TogglePause => self.update_playhead(if self.offset.is_live() {
                 timeline::Playhead::Paused(self.timeline.end())
             } else {
                 timeline::Playhead::Live
             }),
-            Message::Previous => self.update_playhead(match self.offset {
+            Message::Previous(steps) => self.update_playhead(match self.offset {
                 timeline::Playhead::Live => timeline::Playhead::Paused(self.timeline.end()),
-                timeline::Playhead::Paused(index) => timeline::Playhead::Paused(index - 1),
+                timeline::Playhead::Paused(index) => {
+                    timeline::Playhead::Paused((index - steps).max(*self.timeline.range().start()))
+                }
             }),
-            Message::Next => self.update_playhead(match self.offset {
+            Message::Next(steps) => self.update_playhead(match self.offset {
                 timeline::Playhead::Live => timeline::Playhead::Live,
                 timeline::Playhead::Paused(index) => {
-                    if index + 1 >= self.timeline.end() {
+                    if index + steps >= self.timeline.end() {
                         timeline::Playhead::Live
                     } else {
-                        timeline::Playhead::Paused(index + 1)
+                        timeline::Playhead::Paused(index + steps)
                     }
                 }
             }),
+            Message::First => {
+                self.update_playhead(timeline::Playhead::Paused(*self.timeline.range().start()))
+            }
+            Message::PreviousUpdate => {
+                let current = self.timeline.index(self.offset);
+
+                match self.timeline.updates(current - 1).next() {
+                    Some(update) => self.update_playhead(timeline::Playhead::Paused(update.index)),
+                    None => Task::none(),
+                }
+            }
+            Message::NextUpdate => {
+                let timeline::Playhead::Paused(current) = self.offset else {
+                    return Task::none();
+                };
+
+                self.update_playhead(match self.timeline.next_update(current) {
+                    Some(update) => timeline::Playhead::Paused(update.index),
+                    None => timeline::Playhead::Live,
+                })
+            }
+            Message::TogglePlayback => {
+                if self.playback.take().is_some() {
+                    return Task::none();
+                }
+
+                if let timeline::Playhead::Paused(index) = self.offset {
+                    self.playback =
+                        Playback::new(self.speed, &self.timeline, index, Instant::now());
+                }
+
+                Task::none()
+            }
+            Message::PlaybackSpeedChanged(speed) => {
+                self.speed = speed;
+
+                if let Some(playback) = &mut self.playback {
+                    playback.set_speed(speed, &self.timeline, Instant::now());
+                }
+
+                Task::none()
+            }
+            Message::PlaybackTicked(now) => {
+                let Some(playback) = &mut self.playback else {
+                    return Task::none();
+                };
+
+                match playback.advance(&self.timeline, self.offset, now) {
+                    Some(timeline::Playhead::Live) => {
+                        self.playback = None;
+                        self.update_playhead(timeline::Playhead::Live)
+                    }
+                    Some(playhead) => self.update_playhead(playhead),
+                    None => {
+                        if self.offset.is_live() {
+                            self.playback = None;
+                        }
+
+                        Task::none()
+                    }
+                }
+            }
             Message::GoLive => self.update_playhead(timeline::Playhead::Live),
             Message::ShowOverview => {
                 self.screen = Screen::Overview(screen::Overview::new());
@@ -216,6 +738,62 @@ impl Comet {
 
                 Task::none()
             }
+            Message::ShowFlamegraph => {
+                self.screen = Screen::Flamegraph(screen::Flamegraph::new());
+
+                Task::none()
+            }
+            Message::ShowOffenders => {
+                self.screen = Screen::Offenders(screen::Offenders::new());
+
+                Task::none()
+            }
+            Message::ShowStats => {
+                self.screen = Screen::Stats(screen::Stats::new());
+
+                Task::none()
+            }
+            Message::ShowBoot => {
+                self.screen = Screen::Boot(screen::Boot::new(&self.timeline));
+
+                Task::none()
+            }
+            Message::ShowPalette => {
+                self.screen = Screen::Palette(screen::Palette::new());
+
+                Task::none()
+            }
+            Message::ShowMessages => {
+                self.screen = Screen::Messages(screen::Messages::new());
+
+                Task::none()
+            }
+            Message::ShowBoard => {
+                self.screen = Screen::Board(screen::Board::new(&self.boards.active().layout));
+
+                Task::none()
+            }
+            Message::SwitchBoard(index) => {
+                if self.boards.select(index) {
+                    return Task::done(Message::ShowBoard);
+                }
+
+                Task::none()
+            }
+            Message::ToggleMaximized => {
+                if let Screen::Board(board) = &mut self.screen {
+                    board.toggle_maximized();
+                }
+
+                Task::none()
+            }
+            Message::ShowCompare => {
+                if self.comparison.is_some() {
+                    self.screen = Screen::Compare(screen::Compare::new());
+                }
+
+                Task::none()
+            }
             Message::Custom(message) => {
                 let Screen::Custom(custom) = &mut self.screen else {
                     return Task::none();
@@ -231,92 +809,893 @@ impl Comet {
                     Task::none()
                 }
             }
-            Message::Chart(interaction) => self.interact_with_chart(interaction),
-            Message::IncrementBarWidth => {
-                self.zoom = self.zoom.increment();
-                self.screen.invalidate();
+            Message::Update(message) => {
+                let Screen::Update(update) = &mut self.screen else {
+                    return Task::none();
+                };
+
+                match update.update(message) {
+                    Some(update::Event::ChartInteracted(interaction)) => {
+                        self.interact_with_chart(interaction)
+                    }
+                    None => Task::none(),
+                }
+            }
+            Message::Board(message) => {
+                let Screen::Board(board) = &mut self.screen else {
+                    return Task::none();
+                };
+
+                match board.update(message) {
+                    Some(board::Event::ChartInteracted(interaction)) => {
+                        self.interact_with_chart(interaction)
+                    }
+                    Some(board::Event::LayoutChanged) => {
+                        self.boards.active_mut().layout = board.layout();
+                        self.boards_changed_at = Some(Instant::now());
+
+                        Task::none()
+                    }
+                    Some(board::Event::Detached(module)) => {
+                        let (window, open) = window::open(window::Settings {
+                            size: Size::new(400.0, 250.0),
+                            level: window::Level::AlwaysOnTop,
+                            ..window::Settings::default()
+                        });
+
+                        let _ = self.popouts.insert(window, board::Pane::new(module));
+
+                        open.discard()
+                    }
+                    Some(board::Event::Selected(index)) => Task::done(Message::SwitchBoard(index)),
+                    Some(board::Event::Created) => {
+                        self.boards.create();
+                        self.save_boards();
+
+                        Task::done(Message::ShowBoard)
+                    }
+                    Some(board::Event::Renamed(name)) => {
+                        self.boards.active_mut().name = name;
+                        self.boards_changed_at = Some(Instant::now());
+
+                        Task::none()
+                    }
+                    Some(board::Event::Deleted) => {
+                        self.boards.delete();
+                        self.save_boards();
+
+                        Task::done(Message::ShowBoard)
+                    }
+                    None => Task::none(),
+                }
+            }
+            Message::Offenders(message) => {
+                if let Screen::Offenders(offenders) = &mut self.screen {
+                    offenders.update(message);
+                }
+
+                Task::none()
+            }
+            Message::Messages(message) => {
+                let Screen::Messages(messages) = &mut self.screen else {
+                    return Task::none();
+                };
+
+                match messages.update(message, self.timeline.index(self.offset)) {
+                    Some(messages::Event::FilterChanged(filter)) => {
+                        self.filter.messages = filter;
+                    }
+                    Some(messages::Event::FilterRateToggled(is_enabled)) => {
+                        self.filter.message_rate = is_enabled;
+                    }
+                    Some(messages::Event::BreakpointAdded(breakpoint)) => {
+                        self.filter.breakpoints.push(breakpoint);
+                    }
+                    Some(messages::Event::BreakpointRemoved(index)) => {
+                        if index < self.filter.breakpoints.len() {
+                            let _ = self.filter.breakpoints.remove(index);
+                        }
+                    }
+                    Some(messages::Event::RewindToggled(is_enabled)) => {
+                        self.filter.rewind = is_enabled;
+                    }
+                    Some(messages::Event::Pinned(index)) => {
+                        return self.interact_with_chart(chart::Interaction::Pinned(index));
+                    }
+                    Some(messages::Event::CopyRequested) => {
+                        return iced::clipboard::write(messages.to_text(
+                            &self.timeline,
+                            self.offset,
+                            &self.filter.messages,
+                        ));
+                    }
+                    Some(messages::Event::ExportRequested) => {
+                        let State::Working { name, .. } = &self.state else {
+                            return Task::none();
+                        };
+
+                        let directory = self.options.auto_save.clone().unwrap_or_default();
+                        let path = session::path(
+                            &directory,
+                            &format!("{name} - Messages"),
+                            SystemTime::now(),
+                        )
+                        .with_extension("csv");
+
+                        let result = path
+                            .parent()
+                            .map_or(Ok(()), fs::create_dir_all)
+                            .and_then(|_| export::messages(&self.timeline, &path));
+
+                        match result {
+                            Ok(()) => log::info!("Messages exported to {}", path.display()),
+                            Err(error) => {
+                                log::error!(
+                                    "Failed to export messages {}: {error}",
+                                    path.display()
+                                );
+                            }
+                        }
+                    }
+                    None => {}
+                }
+
+                Task::none()
+            }
+            Message::Chart(interaction) => self.interact_with_chart(interaction),
+            Message::Compare(interaction) => {
+                // Both recordings are static, so only zooming applies to them
+                if let chart::Interaction::ZoomChanged(zoom) = interaction {
+                    self.chart.zoom = zoom;
+                    self.invalidate();
+                }
+
+                Task::none()
+            }
+            Message::IncrementBarWidth => {
+                self.chart.zoom = self.chart.zoom.increment();
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::DecrementBarWidth => {
+                self.chart.zoom = self.chart.zoom.decrement();
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::ToggleHistogram => {
+                self.chart.mode = self.chart.mode.toggle();
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::ToggleAxis => {
+                self.chart.axis = self.chart.axis.toggle();
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::ToggleAnomalies => {
+                self.chart.anomalies = !self.chart.anomalies;
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::PreviousAnomaly | Message::NextAnomaly => {
+                let current = self.timeline.index(self.offset);
+
+                let anomaly = if let Message::PreviousAnomaly = message {
+                    self.anomalies().find(|index| *index < current)
+                } else {
+                    self.anomalies().take_while(|index| *index > current).last()
+                };
+
+                match anomaly {
+                    Some(index) => self.update_playhead(timeline::Playhead::Paused(index)),
+                    None => Task::none(),
+                }
+            }
+            Message::ToggleBookmark => {
+                let index = self.timeline.index(self.offset);
+
+                if self.bookmarks.remove(&index).is_none() {
+                    let label = format!("Bookmark {}", self.bookmarks.len() + 1);
+                    let _ = self.bookmarks.insert(index, label);
+                }
+
+                Task::none()
+            }
+            Message::PreviousBookmark | Message::NextBookmark => {
+                let current = self.timeline.index(self.offset);
+
+                let bookmark = if let Message::PreviousBookmark = message {
+                    self.bookmarks.range(..current).next_back()
+                } else {
+                    self.bookmarks.range(current + 1..).next()
+                };
+
+                match bookmark {
+                    Some((index, _)) => self.update_playhead(timeline::Playhead::Paused(*index)),
+                    None => Task::none(),
+                }
+            }
+            Message::MarkerSelected(index) => {
+                self.update_playhead(timeline::Playhead::Paused(index))
+            }
+            Message::BookmarkRenamed(index, label) => {
+                if let Some(bookmark) = self.bookmarks.get_mut(&index) {
+                    *bookmark = label;
+                }
+
+                Task::none()
+            }
+            Message::CycleBudget => {
+                self.chart.budget = chart::Budget::cycle(self.chart.budget);
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::WindowUnfocused(id) => window::is_minimized(id).map(move |is_minimized| {
+                Message::VisibilityChanged(id, !is_minimized.unwrap_or(false))
+            }),
+            Message::WindowClosed(id) => {
+                if id == self.window {
+                    self.flush_recorder();
+                    self.flush_boards();
+                    self.save_settings();
+
+                    return iced::exit();
+                }
+
+                let _ = self.popouts.remove(&id);
+
+                Task::none()
+            }
+            Message::WindowMoved(id, position) => {
+                if id == self.window && !self.is_hidden {
+                    let geometry = self.settings.window.get_or_insert(config::Geometry {
+                        x: 0.0,
+                        y: 0.0,
+                        width: 800.0,
+                        height: 600.0,
+                    });
+
+                    geometry.x = position.x;
+                    geometry.y = position.y;
+                }
+
+                Task::none()
+            }
+            Message::WindowResized(id, size) => {
+                let is_visible = size.width > 0.0 && size.height > 0.0;
+
+                if id == self.window && is_visible {
+                    let geometry = self.settings.window.get_or_insert(config::Geometry {
+                        x: 0.0,
+                        y: 0.0,
+                        width: size.width,
+                        height: size.height,
+                    });
+
+                    geometry.width = size.width;
+                    geometry.height = size.height;
+                }
+
+                Task::done(Message::VisibilityChanged(id, is_visible))
+            }
+            Message::VisibilityChanged(id, is_visible) => {
+                if id != self.window {
+                    return Task::none();
+                }
+
+                if is_visible && self.is_hidden {
+                    self.screen.invalidate();
+                }
+
+                self.is_hidden = !is_visible;
+
+                Task::none()
+            }
+            Message::ClearTimeline => {
+                self.clear();
+
+                Task::none()
+            }
+            Message::ToggleFreeze => match self.frozen.take() {
+                Some(backlog) => {
+                    let task = self.resume(backlog);
+                    self.invalidate();
+
+                    task
+                }
+                None => {
+                    self.frozen = Some(Backlog::default());
+
+                    Task::none()
+                }
+            },
+            Message::CycleTail => {
+                self.chart.tail = chart::Tail::cycle(self.chart.tail);
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::IncrementBuckets | Message::DecrementBuckets => {
+                if let chart::Mode::Histogram { buckets } = &mut self.chart.mode {
+                    *buckets = if let Message::IncrementBuckets = message {
+                        buckets.increment()
+                    } else {
+                        buckets.decrement()
+                    };
+
+                    self.invalidate();
+                }
+
+                Task::none()
+            }
+            Message::ThemeSelected(choice) => {
+                self.appearance.select(choice);
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::SystemThemeChanged(mode) => {
+                let theme = self.appearance.theme();
+                self.appearance.set_mode(mode);
+
+                if self.appearance.theme() != theme {
+                    self.invalidate();
+                }
+
+                Task::none()
+            }
+            Message::FollowAppToggled(follows_app) => {
+                self.appearance.set_follows_app(follows_app);
+                self.invalidate();
+
+                Task::none()
+            }
+            Message::KeyPressed(key, modifiers) => match self.keymap.action(&key, modifiers) {
+                Some(action) => self.update(Message::from(action)),
+                None => Task::none(),
+            },
+            Message::ToggleHelp => {
+                self.is_help_open = !self.is_help_open;
+
+                Task::none()
+            }
+            Message::Quit => {
+                self.flush_recorder();
+                self.flush_boards();
+                self.save_settings();
+
+                iced::exit()
+            }
+        }
+    }
+
+    fn save_boards(&mut self) {
+        config::save(BOARDS, &self.boards);
+        self.boards_changed_at = None;
+    }
+
+    /// Saves the boards if they have changes that are still waiting to
+    /// settle.
+    fn flush_boards(&mut self) {
+        if self.boards_changed_at.is_some() {
+            self.save_boards();
+        }
+    }
+
+    /// Saves the current preferences, so the next run starts where this one
+    /// left off.
+    fn save_settings(&self) {
+        let settings = config::Settings {
+            theme: Some(self.appearance.fallback().to_string()),
+            follow_app: Some(self.appearance.follows_app()),
+            zoom: Some(self.chart.zoom),
+            frame_budget: self
+                .chart
+                .budget
+                .map(|budget| budget.duration().as_secs_f64() * 1_000.0),
+            board: self.boards.active_index(),
+            ..self.settings.clone()
+        };
+
+        config::save(SETTINGS, &settings);
+    }
+
+    /// Invalidates the charts of the screen and of every popped out module.
+    fn invalidate(&mut self) {
+        self.screen.invalidate();
+
+        for pane in self.popouts.values_mut() {
+            pane.invalidate();
+        }
+    }
+
+    /// Returns the indices of the anomalous frames in the timeline, newest
+    /// first.
+    fn anomalies(&self) -> impl Iterator<Item = timeline::Index> {
+        let frames: Vec<_> = self.timeline.frames(timeline::Playhead::Live).collect();
+
+        let totals: Vec<f64> = frames
+            .iter()
+            .map(|frame| frame.total().as_secs_f64())
+            .collect();
+
+        frames
+            .into_iter()
+            .zip(stats::anomalies(&totals))
+            .filter_map(|(frame, is_anomaly)| is_anomaly.then_some(frame.index))
+    }
+
+    fn interact_with_chart(&mut self, interaction: chart::Interaction) -> Task<Message> {
+        match interaction {
+            chart::Interaction::Hovered(index) => self.rewind(index),
+            chart::Interaction::Selected(index) => {
+                if let timeline::Playhead::Live = self.offset {
+                    self.offset = timeline::Playhead::Paused(self.timeline.end());
+                }
+
+                self.selection = timeline::Playhead::Paused(index);
+                self.invalidate();
+
+                Task::none()
+            }
+            chart::Interaction::Pinned(index) => {
+                if let timeline::Playhead::Live = self.offset {
+                    self.offset = timeline::Playhead::Paused(self.timeline.end());
+                }
+
+                self.selection = timeline::Playhead::Paused(index);
+                self.pinned = Some(index);
+                self.invalidate();
+
+                self.rewind(index)
+            }
+            chart::Interaction::Unhovered => match self.pinned {
+                Some(index) => self.rewind(index),
+                None => self.go_live(),
+            },
+            chart::Interaction::ZoomChanged(zoom) => {
+                self.chart.zoom = zoom;
+                self.invalidate();
+
+                Task::none()
+            }
+            chart::Interaction::ExportRequested(stage) => {
+                let State::Working { name, .. } = &self.state else {
+                    return Task::none();
+                };
+
+                let title = format!("{name} - {stage}");
+                let directory = self.options.auto_save.clone().unwrap_or_default();
+                let path =
+                    session::path(&directory, &title, SystemTime::now()).with_extension("svg");
+
+                let theme = self.appearance.theme();
+
+                let result = path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| {
+                        export::chart(&path, &title, &theme, |svg, size| {
+                            chart::render(svg, size, stage, self.chart_context(), theme.palette());
+                        })
+                    });
+
+                match result {
+                    Ok(()) => log::info!("Chart exported to {}", path.display()),
+                    Err(error) => log::error!("Failed to export chart {}: {error}", path.display()),
+                }
+
+                Task::none()
+            }
+        }
+    }
+
+    /// Wipes the timeline and everything pointing into it.
+    fn clear(&mut self) {
+        self.offset = timeline::Playhead::Live;
+        self.selection = timeline::Playhead::Live;
+        self.pinned = None;
+        self.timeline.clear();
+        self.bookmarks.clear();
+        self.playback = None;
+        self.hovered = None;
+
+        // Indices start over, so the recording must too
+        if let Some(recording) = &mut self.recording {
+            recording.restart(self.timeline.end());
+        }
+
+        if let Some(backlog) = &mut self.frozen {
+            *backlog = Backlog::default();
+        }
+
+        self.chart.windows = chart::Windows::All;
+        self.screen.reset(&self.timeline);
+
+        for pane in self.popouts.values_mut() {
+            pane.invalidate();
+        }
+    }
+
+    /// Lists every keyboard shortcut currently bound.
+    fn help(&self) -> Element<'_, Message> {
+        let shortcuts = column(self.keymap.help().map(|(description, binding)| {
+            row![
+                text(description).size(12).width(Fill),
+                text(binding.to_string()).size(12).font(Font::MONOSPACE),
+            ]
+            .spacing(10)
+            .into()
+        }))
+        .spacing(5)
+        .padding(padding::all(10).top(5));
+
+        let close = button(text("Close").size(12))
+            .padding([2, 5])
+            .on_press(Message::ToggleHelp)
+            .style(button::text);
+
+        center(
+            container(card_with_action(
+                self.keymap
+                    .hint("Keyboard shortcuts", keymap::Action::ToggleHelp),
+                close,
+                scrollable(shortcuts),
+            ))
+            .max_width(400),
+        )
+        .padding(40)
+        .into()
+    }
+
+    /// Describes the timeline at the given index, for previewing it while
+    /// hovering the slider.
+    fn preview(&self, index: timeline::Index) -> Element<'_, Message> {
+        const MAX_LENGTH: usize = 60;
+
+        let at = self
+            .timeline
+            .time_at(timeline::Playhead::Paused(index))
+            .map(|at| {
+                let datetime: chrono::DateTime<chrono::Local> = at.into();
+
+                datetime.format("%H:%M:%S%.3f").to_string()
+            })
+            .unwrap_or_default();
+
+        let update = self.timeline.updates(index).next().map(|update| {
+            let message = if update.message.chars().count() > MAX_LENGTH {
+                format!(
+                    "{}…",
+                    update.message.chars().take(MAX_LENGTH).collect::<String>()
+                )
+            } else {
+                update.message.to_string()
+            };
+
+            column![
+                text!("Update #{}", update.number).size(10),
+                text(message).size(10).font(Font::MONOSPACE),
+            ]
+        });
+
+        column![text!("{at} · Event #{index}").size(10), update]
+            .spacing(2)
+            .into()
+    }
+
+    fn update_playhead(&mut self, playhead: timeline::Playhead) -> Task<Message> {
+        self.offset = playhead;
+        self.invalidate();
+
+        match playhead {
+            timeline::Playhead::Live => {
+                self.selection = timeline::Playhead::Live;
+                self.pinned = None;
+                self.go_live()
+            }
+            timeline::Playhead::Paused(index) => self.rewind(index),
+        }
+    }
+
+    fn rewind(&mut self, playhead: timeline::Index) -> Task<Message> {
+        let State::Working {
+            connection: Connection::Connected { client, .. },
+            can_time_travel: true,
+            ..
+        } = &self.state
+        else {
+            return Task::none();
+        };
+
+        let update = self.timeline.updates(playhead).next();
+
+        if let Some(update) = update {
+            Task::future(client.rewind_to(update.number)).discard()
+        } else {
+            Task::none()
+        }
+    }
+
+    fn go_live(&mut self) -> Task<Message> {
+        let State::Working {
+            connection: Connection::Connected { client, .. },
+            can_time_travel: true,
+            ..
+        } = &self.state
+        else {
+            return Task::none();
+        };
+
+        Task::future(client.go_live()).discard()
+    }
+
+    /// Writes the events recorded so far to disk, stopping the recorder if
+    /// that fails.
+    fn flush_recorder(&mut self) {
+        if let Some(recorder) = &mut self.recorder
+            && let Err(error) = recorder.flush()
+        {
+            log::error!("Failed to record events: {error}");
+            self.recorder = None;
+        }
+    }
+
+    /// Ingests an event reported by the beacon.
+    fn report(&mut self, event: beacon::Event) -> Task<Message> {
+        if let Some(recorder) = &mut self.recorder
+            && let Err(error) = recorder.record(&event)
+        {
+            log::error!("Failed to record event: {error}");
+            self.recorder = None;
+        }
+
+        if let Some(exporter) = &self.exporter
+            && !exporter.export(&event)
+        {
+            log::error!("OTLP exporter stopped unexpectedly");
+            self.exporter = None;
+        }
+
+        match event.clone() {
+            beacon::Event::Connected {
+                connection,
+                at,
+                name,
+                version,
+                theme,
+                can_time_travel,
+                ..
+            } => {
+                let current_name = match &self.state {
+                    State::Working { name, .. } => Some(name),
+                    State::Waiting => None,
+                };
+
+                if Some(&name) != current_name {
+                    self.clear();
+                }
+
+                if let Some(palette) = theme {
+                    self.appearance.adopt(Theme::custom(name.clone(), palette));
+                }
+
+                if self.options.auto_save.is_some() && self.recording.is_none() {
+                    self.recording = Some(session::Recording {
+                        start: self.timeline.end(),
+                        started_at: at,
+                    });
+                }
+
+                self.state = State::Working {
+                    name,
+                    can_time_travel,
+                    connection: Connection::Connected {
+                        client: connection,
+                        version,
+                    },
+                };
+            }
+            beacon::Event::Disconnected { at } => {
+                if let State::Working { connection, .. } = &mut self.state {
+                    *connection = Connection::Disconnected { at };
+                }
+            }
+            beacon::Event::ThemeChanged { seed, .. } => {
+                if let State::Working { name, .. } = &self.state {
+                    self.appearance.adopt(Theme::custom(name.clone(), seed));
+                }
+            }
+            beacon::Event::SpanFinished { .. } => {}
+            beacon::Event::QuitRequested { .. } | beacon::Event::AlreadyRunning { .. } => {
+                self.flush_recorder();
+                self.flush_boards();
+                self.save_recording();
+                self.save_settings();
 
-                Task::none()
+                return iced::exit();
             }
-            Message::DecrementBarWidth => {
-                self.zoom = self.zoom.decrement();
-                self.screen.invalidate();
+        }
 
-                Task::none()
+        if let Some(backlog) = &mut self.frozen {
+            let is_disconnect = matches!(event, beacon::Event::Disconnected { .. });
+
+            backlog.push(event);
+
+            if is_disconnect {
+                // The session must include what was received while frozen
+                let backlog = std::mem::take(backlog);
+                let task = self.resume(backlog);
+                self.invalidate();
+
+                return task;
             }
-            Message::Quit => iced::exit(),
+
+            return Task::none();
         }
+
+        self.ingest(event)
     }
 
-    fn interact_with_chart(&mut self, interaction: chart::Interaction) -> Task<Message> {
-        match interaction {
-            chart::Interaction::Hovered(index) => self.rewind(index),
-            chart::Interaction::Selected(index) => {
-                if let timeline::Playhead::Live = self.offset {
-                    self.offset = timeline::Playhead::Paused(self.timeline.end());
-                }
+    /// Pushes the events received while frozen to the timeline, as if they
+    /// had just been reported.
+    fn resume(&mut self, backlog: Backlog) -> Task<Message> {
+        if backlog.dropped > 0 {
+            log::warn!(
+                "{} events received while frozen were dropped",
+                backlog.dropped
+            );
+        }
 
-                self.selection = timeline::Playhead::Paused(index);
-                self.screen.invalidate();
+        Task::batch(backlog.events.into_iter().map(|event| self.ingest(event)))
+    }
 
-                Task::none()
-            }
-            chart::Interaction::Unhovered => self.go_live(),
-            chart::Interaction::ZoomChanged(zoom) => {
-                self.zoom = zoom;
-                self.screen.invalidate();
+    /// Pushes a reported event to the timeline, invalidating whatever
+    /// depends on it and stopping at any breakpoint.
+    fn ingest(&mut self, event: beacon::Event) -> Task<Message> {
+        let is_disconnect = matches!(event, beacon::Event::Disconnected { .. });
 
-                Task::none()
+        let is_breakpoint = self.offset.is_live()
+            && matches!(
+                &event,
+                beacon::Event::SpanFinished {
+                    span: beacon::Span::Update { message, .. },
+                    ..
+                } if self.filter.breaks_on(message)
+            );
+
+        if !self.is_hidden {
+            self.screen.invalidate_by(&event);
+        }
+
+        for pane in self.popouts.values_mut() {
+            pane.invalidate_by(&event);
+        }
+
+        self.timeline.push(event);
+
+        if is_disconnect {
+            self.save_recording();
+            self.recording = None;
+        }
+
+        if is_breakpoint {
+            let index = self.timeline.end();
+
+            self.offset = timeline::Playhead::Paused(index);
+            self.selection = timeline::Playhead::Paused(index);
+            self.pinned = Some(index);
+            self.invalidate();
+
+            if self.filter.rewind {
+                return self.rewind(index);
             }
         }
+
+        Task::none()
     }
 
-    fn update_playhead(&mut self, playhead: timeline::Playhead) -> Task<Message> {
-        self.offset = playhead;
-        self.screen.invalidate();
+    fn replay(&mut self, entry: session::Entry, path: &Path) {
+        match entry {
+            session::Entry::Connected { name, theme, .. } => {
+                let is_same_app =
+                    matches!(&self.state, State::Working { name: current, .. } if *current == name);
 
-        match playhead {
-            timeline::Playhead::Live => {
-                self.selection = timeline::Playhead::Live;
-                self.go_live()
+                if !is_same_app {
+                    self.clear();
+                }
+
+                if let Some(palette) = theme {
+                    self.appearance.adopt(Theme::custom(name.clone(), palette));
+                }
+
+                self.state = State::Working {
+                    name,
+                    can_time_travel: false,
+                    connection: Connection::Session {
+                        path: path.to_path_buf(),
+                    },
+                };
+            }
+            entry => {
+                let Some(event) = entry.into_event() else {
+                    return;
+                };
+
+                if let (beacon::Event::ThemeChanged { seed, .. }, State::Working { name, .. }) =
+                    (&event, &self.state)
+                {
+                    self.appearance.adopt(Theme::custom(name.clone(), *seed));
+                }
+
+                if !self.is_hidden {
+                    self.screen.invalidate_by(&event);
+                }
+
+                for pane in self.popouts.values_mut() {
+                    pane.invalidate_by(&event);
+                }
+
+                self.timeline.push(event);
             }
-            timeline::Playhead::Paused(index) => self.rewind(index),
         }
     }
 
-    fn rewind(&mut self, playhead: timeline::Index) -> Task<Message> {
-        let State::Working {
-            connection: Connection::Connected { client, .. },
-            can_time_travel: true,
-            ..
-        } = &self.state
+    fn save_recording(&self) {
+        let (Some(directory), Some(recording), State::Working { name, .. }) =
+            (&self.options.auto_save, &self.recording, &self.state)
         else {
-            return Task::none();
+            return;
         };
 
-        let update = self.timeline.updates(playhead).next();
+        self.save_session(
+            &session::path(directory, name, recording.started_at),
+            recording.start,
+        );
+    }
 
-        if let Some(update) = update {
-            Task::future(client.rewind_to(update.number)).discard()
-        } else {
-            Task::none()
+    fn save_session(&self, path: &Path, start: timeline::Index) {
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| session::save(path, self.timeline.restore(start)));
+
+        match result {
+            Ok(()) => log::info!("Session saved to {}", path.display()),
+            Err(error) => log::error!("Failed to save session {}: {error}", path.display()),
         }
     }
 
-    fn go_live(&mut self) -> Task<Message> {
-        let State::Working {
-            connection: Connection::Connected { client, .. },
-            can_time_travel: true,
-            ..
-        } = &self.state
-        else {
-            return Task::none();
-        };
-
-        Task::future(client.go_live()).discard()
+    fn chart_context(&self) -> chart::Context<'_> {
+        chart::Context {
+            timeline: &self.timeline,
+            offset: self.offset,
+            selection: self.selection,
+            settings: self.chart,
+            baseline: &self.baseline,
+        }
     }
 
-    fn view(&self) -> Element<'_, Message> {
+    fn view(&self, window: window::Id) -> Element<'_, Message> {
+        if let Some(pane) = self.popouts.get(&window) {
+            return container(card(
+                pane.module().to_string(),
+                pane.view(
+                    self.chart_context(),
+                    self.filter.message_rate.then_some(&self.filter.messages),
+                )
+                .map(Message::Chart),
+            ))
+            .padding(10)
+            .into();
+        }
+
         match &self.state {
             State::Waiting => center(
                 row![
@@ -343,6 +1722,7 @@ impl Comet {
                     let status = circle(move |palette| match connection {
                         Connection::Connected { .. } => palette.success.base.color,
                         Connection::Disconnected { .. } => palette.danger.base.color,
+                        Connection::Session { .. } => palette.primary.base.color,
                     });
 
                     let time = if let Some(time) = self.timeline.time_at(self.offset) {
@@ -365,6 +1745,43 @@ impl Comet {
                         Element::from(space::horizontal())
                     };
 
+                    let runs = if self.timeline.runs().nth(1).is_some() {
+                        pick_list(
+                            self.timeline.runs().copied().collect::<Vec<_>>(),
+                            self.timeline.run_at(self.offset).copied(),
+                            Message::RunSelected,
+                        )
+                        .text_size(10)
+                        .padding([2, 5])
+                        .into()
+                    } else {
+                        Element::from(space::horizontal())
+                    };
+
+                    let windows = if self.timeline.windows().len() > 1
+                        && matches!(
+                            self.screen,
+                            Screen::Overview(_) | Screen::Present(_) | Screen::Board(_)
+                        ) {
+                        let options: Vec<_> = [chart::Windows::All]
+                            .into_iter()
+                            .chain(
+                                self.timeline
+                                    .windows()
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(i, id)| chart::Windows::Only(i + 1, *id)),
+                            )
+                            .collect();
+
+                        pick_list(options, Some(self.chart.windows), Message::WindowsSelected)
+                            .text_size(10)
+                            .padding([2, 5])
+                            .into()
+                    } else {
+                        Element::from(space::horizontal())
+                    };
+
                     let tabs = {
                         fn tab<'a>(
                             label: &'static str,
@@ -411,40 +1828,225 @@ impl Comet {
                                 "Custom",
                                 Message::ShowCustom,
                                 matches!(self.screen, Screen::Custom(_))
+                            ),
+                            tab(
+                                "Flamegraph",
+                                Message::ShowFlamegraph,
+                                matches!(self.screen, Screen::Flamegraph(_))
+                            ),
+                            tab(
+                                "Offenders",
+                                Message::ShowOffenders,
+                                matches!(self.screen, Screen::Offenders(_))
+                            ),
+                            tab(
+                                "Stats",
+                                Message::ShowStats,
+                                matches!(self.screen, Screen::Stats(_))
+                            ),
+                            tab(
+                                "Boot",
+                                Message::ShowBoot,
+                                matches!(self.screen, Screen::Boot(_))
+                            ),
+                            tab(
+                                "Palette",
+                                Message::ShowPalette,
+                                matches!(self.screen, Screen::Palette(_))
+                            ),
+                            tab(
+                                "Messages",
+                                Message::ShowMessages,
+                                matches!(self.screen, Screen::Messages(_))
+                            ),
+                            tab(
+                                "Board",
+                                Message::ShowBoard,
+                                matches!(self.screen, Screen::Board(_))
                             )
                         ]
+                        .extend(self.comparison.is_some().then(|| {
+                            tab(
+                                "Compare",
+                                Message::ShowCompare,
+                                matches!(self.screen, Screen::Compare(_)),
+                            )
+                        }))
                         .spacing(10)
                         .align_y(Center)
                     };
 
-                    row![logo, status, time, time_travel, space::horizontal(), tabs]
+                    let save = tip(
+                        button(text("Save").size(12))
+                            .padding([2, 5])
+                            .on_press(Message::SaveSession)
+                            .style(button::text),
+                        self.keymap
+                            .hint("Save session", keymap::Action::SaveSession),
+                        tooltip::Position::Bottom,
+                    );
+
+                    let appearance = row![
+                        pick_list(
+                            appearance::Choice::all(),
+                            Some(self.appearance.choice().clone()),
+                            Message::ThemeSelected,
+                        )
+                        .text_size(10)
+                        .padding([2, 5]),
+                        tip(
+                            toggler(self.appearance.follows_app())
+                                .label("Follow app")
+                                .on_toggle(Message::FollowAppToggled)
+                                .text_size(10)
+                                .size(12),
+                            "Adopt the palette of the app whenever it changes",
+                            tooltip::Position::Bottom,
+                        ),
+                    ]
+                    .spacing(5)
+                    .align_y(Center);
+
+                    let report = tip(
+                        button(text("Report").size(12))
+                            .padding([2, 5])
+                            .on_press(Message::GenerateReport)
+                            .style(button::text),
+                        self.keymap
+                            .hint("Generate report", keymap::Action::GenerateReport),
+                        tooltip::Position::Bottom,
+                    );
+
+                    // The tabs get their own row, scrolling when they don't fit
+                    let tabs = scrollable(tabs).direction(scrollable::Direction::Horizontal(
+                        scrollable::Scrollbar::new().width(2).scroller_width(2),
+                    ));
+
+                    column![
+                        row![
+                            logo,
+                            status,
+                            time,
+                            time_travel,
+                            runs,
+                            windows,
+                            space::horizontal(),
+                            appearance,
+                            report,
+                            save,
+                        ]
                         .spacing(10)
-                        .align_y(Center)
-                        .height(Shrink)
+                        .align_y(Center),
+                        tabs,
+                    ]
+                    .spacing(5)
+                    .height(Shrink)
                 };
 
                 let screen = match &self.screen {
                     Screen::Overview(overview) => overview
-                        .view(&self.timeline, self.offset, self.selection, self.zoom)
+                        .view(
+                            &self.timeline,
+                            self.offset,
+                            self.selection,
+                            self.chart,
+                            &self.baseline,
+                        )
                         .map(Message::Chart),
                     Screen::Update(update) => update
-                        .view(&self.timeline, self.offset, self.selection, self.zoom)
-                        .map(Message::Chart),
+                        .view(
+                            &self.timeline,
+                            self.offset,
+                            self.selection,
+                            self.chart,
+                            &self.baseline,
+                            self.filter.message_rate.then_some(&self.filter.messages),
+                        )
+                        .map(Message::Update),
                     Screen::Present(present) => present
-                        .view(&self.timeline, self.offset, self.selection, self.zoom)
+                        .view(
+                            &self.timeline,
+                            self.offset,
+                            self.selection,
+                            self.chart,
+                            &self.baseline,
+                        )
                         .map(Message::Chart),
                     Screen::Custom(custom) => custom
-                        .view(&self.timeline, self.offset, self.selection, self.zoom)
+                        .view(
+                            &self.timeline,
+                            self.offset,
+                            self.selection,
+                            self.chart,
+                            &self.baseline,
+                        )
                         .map(Message::Custom),
+                    Screen::Flamegraph(flamegraph) => flamegraph
+                        .view(&self.timeline, self.offset, self.selection, self.chart)
+                        .map(Message::Chart),
+                    Screen::Offenders(offenders) => offenders
+                        .view(&self.timeline, self.offset)
+                        .map(Message::Offenders),
+                    Screen::Stats(stats) => stats.view(&self.timeline, self.offset),
+                    Screen::Boot(boot) => boot.view(),
+                    Screen::Messages(messages) => messages
+                        .view(
+                            &self.timeline,
+                            self.offset,
+                            self.selection,
+                            self.chart,
+                            &self.filter,
+                        )
+                        .map(Message::Messages),
+                    Screen::Palette(palette) => palette
+                        .view(
+                            &self.timeline,
+                            self.offset,
+                            self.selection,
+                            &self.appearance.theme(),
+                        )
+                        .map(Message::Chart),
+                    Screen::Board(board) => board
+                        .view(
+                            self.chart_context(),
+                            self.filter.message_rate.then_some(&self.filter.messages),
+                            &self.boards,
+                            &self.keymap,
+                        )
+                        .map(Message::Board),
+                    Screen::Compare(compare) => match &self.comparison {
+                        Some(comparison) => {
+                            compare.view(comparison, self.chart).map(Message::Compare)
+                        }
+                        None => space().into(),
+                    },
                 };
 
                 let timeline = {
-                    let timeline = slider(
-                        self.timeline.range(),
-                        self.timeline.index(self.offset),
-                        Message::PlayheadChanged,
+                    let timeline = tooltip(
+                        stack![
+                            slider(
+                                self.timeline.range(),
+                                self.timeline.index(self.offset),
+                                Message::PlayheadChanged,
+                            ),
+                            hover(self.timeline.range(), Message::PlayheadHovered),
+                        ],
+                        container(
+                            self.preview(self.hovered.unwrap_or(self.timeline.index(self.offset))),
+                        )
+                        .padding(5)
+                        .style(container::rounded_box),
+                        tooltip::Position::FollowCursor,
                     );
 
+                    let memory = match self.options.memory_limit {
+                        Some(limit) => {
+                            format!("{} / {}", Bytes(self.timeline.bytes()), Bytes(limit))
+                        }
+                        None => Bytes(self.timeline.bytes()).to_string(),
+                    };
+
                     let buffer = tip(
                         progress_bar(
                             0.0..=self.timeline.capacity() as f32,
@@ -453,13 +2055,74 @@ impl Comet {
                         .girth(10)
                         .length(20),
                         format!(
-                            "Buffer capacity: {} / {}",
+                            "Buffer capacity: {} / {}\nMemory (estimate): {memory}",
                             self.timeline.len(),
                             self.timeline.capacity(),
                         ),
                         tooltip::Position::Top,
                     );
 
+                    let clear = tip(
+                        button(text("Clear").size(10))
+                            .padding([2, 5])
+                            .on_press_maybe(
+                                (self.timeline.len() > 0).then_some(Message::ClearTimeline),
+                            )
+                            .style(button::secondary),
+                        self.keymap
+                            .hint("Clear the timeline", keymap::Action::ClearTimeline),
+                        tooltip::Position::Top,
+                    );
+
+                    let freeze = tip(
+                        button(
+                            text(match &self.frozen {
+                                Some(backlog) if backlog.dropped > 0 => {
+                                    format!("Resume ({} dropped)", backlog.dropped)
+                                }
+                                Some(_) => String::from("Resume"),
+                                None => String::from("Freeze"),
+                            })
+                            .size(10),
+                        )
+                        .padding([2, 5])
+                        .on_press(Message::ToggleFreeze)
+                        .style(if self.frozen.is_some() {
+                            button::primary
+                        } else {
+                            button::secondary
+                        }),
+                        match &self.frozen {
+                            Some(backlog) => format!(
+                                "{}\n{} events buffered",
+                                self.keymap
+                                    .hint("Resume recording", keymap::Action::ToggleFreeze),
+                                backlog.events.len()
+                            ),
+                            None => self
+                                .keymap
+                                .hint("Stop recording new events", keymap::Action::ToggleFreeze),
+                        },
+                        tooltip::Position::Top,
+                    );
+
+                    // Warn when reaching 90% of the memory limit
+                    let memory_warning = self
+                        .options
+                        .memory_limit
+                        .filter(|limit| self.timeline.bytes() >= limit / 10 * 9)
+                        .map(|limit| {
+                            text!(
+                                "Memory: {} / {}",
+                                Bytes(self.timeline.bytes()),
+                                Bytes(limit)
+                            )
+                            .size(10)
+                            .style(|theme: &Theme| text::Style {
+                                color: Some(theme.palette().warning.base.color),
+                            })
+                        });
+
                     let counter = text!(
                         "{} / {}",
                         self.timeline.index(self.offset),
@@ -467,6 +2130,8 @@ impl Comet {
                     )
                     .size(10);
 
+                    let index = self.timeline.index(self.selection);
+
                     let event = self.timeline.get(self.selection).map(|event| {
                         match event {
                             iced_beacon::Event::Connected { .. } => text("Connected"),
@@ -474,7 +2139,9 @@ impl Comet {
                             iced_beacon::Event::ThemeChanged { .. } => text("Theme Changed"),
                             iced_beacon::Event::SpanFinished { span, .. } => match span {
                                 iced_beacon::Span::Boot => text("Boot"),
-                                iced_beacon::Span::Update { message, .. } => {
+                                iced_beacon::Span::Update { .. } => {
+                                    let message = self.timeline.message(index).unwrap_or_default();
+
                                     text!(
                                         "Update: {}",
                                         message.replace("\n", " ").replace("    ", "")
@@ -522,61 +2189,307 @@ impl Comet {
                         }
                     };
 
+                    let playback = row![
+                        button(
+                            text(if self.playback.is_some() {
+                                "Stop"
+                            } else {
+                                "Play"
+                            })
+                            .size(10),
+                        )
+                        .padding([2, 5])
+                        .on_press_maybe(
+                            (!self.offset.is_live() || self.playback.is_some())
+                                .then_some(Message::TogglePlayback),
+                        )
+                        .style(button::secondary),
+                        pick_list(
+                            playback::Speed::ALL,
+                            Some(self.speed),
+                            Message::PlaybackSpeedChanged,
+                        )
+                        .text_size(10)
+                        .padding([2, 5]),
+                    ]
+                    .spacing(5)
+                    .align_y(Center);
+
+                    let milestones = self
+                        .timeline
+                        .milestones()
+                        .map(|milestone| match *milestone {
+                            timeline::Milestone::Disconnected(index) => markers::Marker {
+                                index,
+                                kind: markers::Kind::Disconnection,
+                            },
+                            timeline::Milestone::ThemeChanged(index) => markers::Marker {
+                                index,
+                                kind: markers::Kind::ThemeChange,
+                            },
+                        });
+
+                    let bookmarks = self.bookmarks.keys().map(|index| markers::Marker {
+                        index: *index,
+                        kind: markers::Kind::Bookmark,
+                    });
+
+                    let markers = markers(
+                        self.timeline.range(),
+                        milestones.chain(bookmarks).collect(),
+                        Message::MarkerSelected,
+                    );
+
+                    let bookmark = self
+                        .bookmarks
+                        .get_key_value(&self.timeline.index(self.offset))
+                        .map(|(index, label)| {
+                            let index = *index;
+
+                            text_input("Bookmark", label)
+                                .on_input(move |label| Message::BookmarkRenamed(index, label))
+                                .size(10)
+                                .padding([2, 5])
+                                .width(150)
+                        });
+
+                    let minimap = minimap(
+                        self.timeline.range(),
+                        self.timeline.density(200),
+                        Message::PlayheadChanged,
+                    );
+
                     column![
-                        timeline,
-                        row![buffer, counter, event, live]
-                            .align_y(Center)
-                            .spacing(10)
+                        column![minimap, timeline, markers],
+                        row![
+                            buffer,
+                            memory_warning,
+                            counter,
+                            bookmark,
+                            event,
+                            playback,
+                            live
+                        ]
+                        .align_y(Center)
+                        .spacing(10)
                     ]
                     .spacing(5)
                 };
 
-                column![header, screen, timeline]
-                    .spacing(10)
-                    .padding(10)
-                    .into()
+                let content = column![header, screen, timeline].spacing(10).padding(10);
+
+                if self.is_help_open {
+                    stack![content, opaque(self.help())].into()
+                } else {
+                    content.into()
+                }
             }
         }
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        let beacon = Subscription::run(beacon::run).map(Message::EventReported);
+        let beacon = if let Some(path) = &self.options.attach {
+            Subscription::run_with(path.clone(), session::tail).map(Message::SessionRead)
+        } else if let Some(path) = &self.options.replay {
+            Subscription::run_with(
+                session::Replay {
+                    path: path.clone(),
+                    speed: self.options.speed.unwrap_or(1.0),
+                },
+                session::replay,
+            )
+            .map(Message::SessionRead)
+        } else if self.options.open.is_some() || self.options.compare.is_some() {
+            Subscription::none()
+        } else {
+            Subscription::run(events).map(Message::EventsReported)
+        };
+
+        let window_events = window::events().filter_map(|(id, event)| match event {
+            window::Event::FileDropped(path) => Some(Message::OpenSession(path)),
+            window::Event::Focused => Some(Message::VisibilityChanged(id, true)),
+            window::Event::Unfocused => Some(Message::WindowUnfocused(id)),
+            window::Event::Closed => Some(Message::WindowClosed(id)),
+            window::Event::Moved(position) => Some(Message::WindowMoved(id, position)),
+            window::Event::Resized(size) => Some(Message::WindowResized(id, size)),
+            _ => None,
+        });
 
         let hotkeys = keyboard::listen().filter_map(|event| {
-            let keyboard::Event::KeyPressed { modified_key, .. } = event else {
+            let keyboard::Event::KeyPressed {
+                modified_key,
+                modifiers,
+                ..
+            } = event
+            else {
                 return None;
             };
 
-            match modified_key.as_ref() {
-                keyboard::Key::Named(keyboard::key::Named::F12) => Some(Message::Quit),
-                keyboard::Key::Named(keyboard::key::Named::Space) => Some(Message::TogglePause),
-                keyboard::Key::Named(keyboard::key::Named::ArrowLeft) => Some(Message::Previous),
-                keyboard::Key::Named(keyboard::key::Named::ArrowRight) => Some(Message::Next),
-                keyboard::Key::Character("o") => Some(Message::ShowOverview),
-                keyboard::Key::Character("u") => Some(Message::ShowUpdate),
-                keyboard::Key::Character("p") => Some(Message::ShowPresent),
-                keyboard::Key::Character("c") => Some(Message::ShowCustom),
-                keyboard::Key::Named(keyboard::key::Named::ArrowUp) => {
-                    Some(Message::IncrementBarWidth)
-                }
-                keyboard::Key::Named(keyboard::key::Named::ArrowDown) => {
-                    Some(Message::DecrementBarWidth)
-                }
-                _ => None,
-            }
+            Some(Message::KeyPressed(modified_key, modifiers))
         });
 
-        Subscription::batch([beacon, hotkeys])
+        let control = if self.options.stdin {
+            Subscription::run(control::listen).map(Message::ControlReceived)
+        } else {
+            Subscription::none()
+        };
+
+        let auto_save = match (self.options.auto_save_every, &self.recording) {
+            (Some(every), Some(_)) => time::every(every).map(|_| Message::AutoSaveElapsed),
+            _ => Subscription::none(),
+        };
+
+        let boards = if self.boards_changed_at.is_some() {
+            time::every(BOARDS_DELAY).map(Message::BoardsSettled)
+        } else {
+            Subscription::none()
+        };
+
+        let playback = if self.playback.is_some() {
+            time::every(time::Duration::from_millis(16)).map(Message::PlaybackTicked)
+        } else {
+            Subscription::none()
+        };
+
+        let system_theme = system::theme_changes().map(Message::SystemThemeChanged);
+
+        Subscription::batch([
+            beacon,
+            hotkeys,
+            window_events,
+            system_theme,
+            control,
+            auto_save,
+            boards,
+            playback,
+        ])
     }
 
-    fn title(&self) -> String {
+    fn title(&self, window: window::Id) -> String {
+        if let Some(pane) = self.popouts.get(&window) {
+            return format!("{} - comet", pane.module());
+        }
+
         match &self.state {
             State::Waiting => String::from("comet"),
             State::Working { name, .. } => format!("{name} - comet"),
         }
     }
 
-    fn theme(&self) -> Theme {
-        self.theme.clone()
+    fn theme(&self, _window: window::Id) -> Theme {
+        self.appearance.theme()
+    }
+}
+
+impl From<keymap::Action> for Message {
+    fn from(action: keymap::Action) -> Self {
+        use keymap::Action;
+
+        match action {
+            Action::Quit => Message::Quit,
+            Action::ToggleHelp => Message::ToggleHelp,
+            Action::TogglePause => Message::TogglePause,
+            Action::PreviousUpdate => Message::PreviousUpdate,
+            Action::NextUpdate => Message::NextUpdate,
+            Action::Previous(amount) => Message::Previous(amount),
+            Action::Next(amount) => Message::Next(amount),
+            Action::First => Message::First,
+            Action::GoLive => Message::GoLive,
+            Action::ShowOverview => Message::ShowOverview,
+            Action::ShowUpdate => Message::ShowUpdate,
+            Action::ShowPresent => Message::ShowPresent,
+            Action::ShowCustom => Message::ShowCustom,
+            Action::ShowFlamegraph => Message::ShowFlamegraph,
+            Action::ShowOffenders => Message::ShowOffenders,
+            Action::ShowStats => Message::ShowStats,
+            Action::ShowBoot => Message::ShowBoot,
+            Action::ShowPalette => Message::ShowPalette,
+            Action::ShowMessages => Message::ShowMessages,
+            Action::ShowCompare => Message::ShowCompare,
+            Action::ShowBoard => Message::ShowBoard,
+            Action::ToggleMaximized => Message::ToggleMaximized,
+            Action::SwitchBoard(board) => Message::SwitchBoard(board),
+            Action::SaveSession => Message::SaveSession,
+            Action::GenerateReport => Message::GenerateReport,
+            Action::ToggleHistogram => Message::ToggleHistogram,
+            Action::CycleBudget => Message::CycleBudget,
+            Action::CycleTail => Message::CycleTail,
+            Action::ClearTimeline => Message::ClearTimeline,
+            Action::ToggleFreeze => Message::ToggleFreeze,
+            Action::ToggleAxis => Message::ToggleAxis,
+            Action::ToggleAnomalies => Message::ToggleAnomalies,
+            Action::PreviousAnomaly => Message::PreviousAnomaly,
+            Action::NextAnomaly => Message::NextAnomaly,
+            Action::TogglePlayback => Message::TogglePlayback,
+            Action::ToggleBookmark => Message::ToggleBookmark,
+            Action::PreviousBookmark => Message::PreviousBookmark,
+            Action::NextBookmark => Message::NextBookmark,
+            Action::IncrementBuckets => Message::IncrementBuckets,
+            Action::DecrementBuckets => Message::DecrementBuckets,
+            Action::IncrementBarWidth => Message::IncrementBarWidth,
+            Action::DecrementBarWidth => Message::DecrementBarWidth,
+        }
+    }
+}
+
+/// The configuration file where the preferences of the user are persisted.
+const SETTINGS: &str = "settings.toml";
+
+/// The configuration file where the boards are persisted.
+const BOARDS: &str = "boards.toml";
+
+/// How long the boards must be left alone before their changes are saved,
+/// so dragging a divider or typing a name doesn't write on every event.
+const BOARDS_DELAY: time::Duration = time::Duration::from_millis(500);
+
+/// Listens to the events of the beacon, reporting every event that is
+/// already waiting at once instead of running an update for each one.
+fn events() -> impl Stream<Item = Vec<beacon::Event>> {
+    const MAX_BATCH: usize = 1_000;
+
+    beacon::run().ready_chunks(MAX_BATCH)
+}
+
+/// The events received while ingestion is frozen, keeping only the latest
+/// ones and counting the rest.
+#[derive(Debug, Default)]
+struct Backlog {
+    events: VecDeque<beacon::Event>,
+    dropped: usize,
+}
+
+impl Backlog {
+    const MAX_EVENTS: usize = 10_000;
+
+    fn push(&mut self, event: beacon::Event) {
+        if self.events.len() == Self::MAX_EVENTS {
+            let _ = self.events.pop_front();
+            self.dropped += 1;
+        }
+
+        self.events.push_back(event);
+    }
+}
+
+/// A human-readable amount of bytes.
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+
+        let mut amount = self.0 as f64;
+        let mut unit = 0;
+
+        while amount >= 1_000.0 && unit < UNITS.len() - 1 {
+            amount /= 1_000.0;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            write!(f, "{} {}", self.0, UNITS[unit])
+        } else {
+            write!(f, "{amount:.1} {}", UNITS[unit])
+        }
     }
 }
@@ -0,0 +1,251 @@
+use crate::beacon;
+use crate::beacon::span::Span;
+use crate::export;
+use crate::stats;
+
+use iced::time::{Duration, SystemTime};
+
+use serde_json::{Value, json};
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::thread;
+
+/// The maximum amount of spans sent in a single request.
+const BATCH_SIZE: usize = 512;
+
+/// How long spans are buffered before being sent.
+const BATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// An OTLP/HTTP collector endpoint, like `http://localhost:4318`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl FromStr for Endpoint {
+    type Err = String;
+
+    fn from_str(endpoint: &str) -> Result<Self, Self::Err> {
+        let Some(address) = endpoint.strip_prefix("http://") else {
+            return Err(format!(
+                "unsupported OTLP endpoint: {endpoint} (expected http://<host>:<port>)"
+            ));
+        };
+
+        let (authority, path) = address
+            .find('/')
+            .map_or((address, ""), |slash| address.split_at(slash));
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|_| format!("invalid OTLP endpoint port: {port}"))?,
+            ),
+            None => (authority, 4318),
+        };
+
+        if host.is_empty() {
+            return Err(format!("invalid OTLP endpoint: {endpoint}"));
+        }
+
+        let path = match path.trim_end_matches('/') {
+            "" => String::from("/v1/traces"),
+            path => path.to_owned(),
+        };
+
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+            path,
+        })
+    }
+}
+
+/// Forwards finished spans to an OTLP collector in the background.
+#[derive(Debug)]
+pub struct Exporter {
+    sender: mpsc::Sender<beacon::Event>,
+}
+
+impl Exporter {
+    pub fn new(endpoint: Endpoint) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        let _ = thread::spawn(move || run(endpoint, receiver));
+
+        Self { sender }
+    }
+
+    /// Queues the event for export, if it is relevant to the collector.
+    ///
+    /// Returns `false` if the exporter has stopped.
+    pub fn export(&self, event: &beacon::Event) -> bool {
+        match event {
+            beacon::Event::Connected { .. } | beacon::Event::SpanFinished { .. } => {
+                self.sender.send(event.clone()).is_ok()
+            }
+            _ => true,
+        }
+    }
+}
+
+fn run(endpoint: Endpoint, receiver: mpsc::Receiver<beacon::Event>) {
+    let random = RandomState::new();
+    let mut service = String::from("iced");
+    let mut trace = id(&random, nanoseconds(SystemTime::now()));
+    let mut spans = Vec::new();
+    let mut exported = 0;
+
+    log::info!(
+        "Exporting spans to http://{}:{}{}",
+        endpoint.host,
+        endpoint.port,
+        endpoint.path
+    );
+
+    loop {
+        let event = receiver.recv_timeout(BATCH_INTERVAL);
+
+        match event {
+            Ok(beacon::Event::Connected { name, .. }) => {
+                flush(&endpoint, &service, &mut spans);
+
+                service = name;
+                trace = id(&random, nanoseconds(SystemTime::now()));
+            }
+            Ok(beacon::Event::SpanFinished { at, duration, span }) => {
+                exported += 1;
+
+                let id = id(&random, exported);
+                spans.push(self::span(&trace, &id[..16], at, duration, &span));
+
+                if spans.len() >= BATCH_SIZE {
+                    flush(&endpoint, &service, &mut spans);
+                }
+            }
+            Ok(_) => {}
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                flush(&endpoint, &service, &mut spans);
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                flush(&endpoint, &service, &mut spans);
+                break;
+            }
+        }
+    }
+}
+
+fn span(trace: &str, id: &str, at: SystemTime, duration: Duration, span: &Span) -> Value {
+    let start = at.checked_sub(duration).unwrap_or(at);
+
+    let mut attributes = vec![attribute("iced.stage", export::stage(span))];
+
+    match span {
+        Span::View { window }
+        | Span::Layout { window }
+        | Span::Interact { window }
+        | Span::Draw { window }
+        | Span::Present { window, .. } => {
+            attributes.push(attribute("iced.window", &format!("{window:?}")));
+        }
+        Span::Update { message, .. } => {
+            attributes.push(attribute("iced.message", message));
+        }
+        _ => {}
+    }
+
+    json!({
+        "traceId": trace,
+        "spanId": id,
+        "name": stats::stage(span),
+        "kind": 1,
+        "startTimeUnixNano": nanoseconds(start).to_string(),
+        "endTimeUnixNano": nanoseconds(at).to_string(),
+        "attributes": attributes,
+    })
+}
+
+fn attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+fn flush(endpoint: &Endpoint, service: &str, spans: &mut Vec<Value>) {
+    if spans.is_empty() {
+        return;
+    }
+
+    let body = json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [attribute("service.name", service)],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "comet" },
+                "spans": std::mem::take(spans),
+            }],
+        }],
+    });
+
+    if let Err(error) = post(endpoint, &body.to_string()) {
+        log::warn!("Failed to export spans: {error}");
+    }
+}
+
+fn post(endpoint: &Endpoint, body: &str) -> io::Result<()> {
+    let mut stream = TcpStream::connect((endpoint.host.as_str(), endpoint.port))?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    write!(
+        stream,
+        "POST {path} HTTP/1.1\r\n\
+         Host: {host}:{port}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {length}\r\n\
+         Connection: close\r\n\
+         \r\n\
+         {body}",
+        path = endpoint.path,
+        host = endpoint.host,
+        port = endpoint.port,
+        length = body.len(),
+    )?;
+
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+
+    let status = response.split_whitespace().nth(1).unwrap_or_default();
+
+    if status.starts_with('2') {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "collector responded with status {status}"
+        )))
+    }
+}
+
+/// Generates a random 128-bit identifier, hex-encoded.
+fn id(random: &RandomState, seed: u128) -> String {
+    let [high, low] = [0, 1].map(|salt| {
+        let mut hasher = random.build_hasher();
+        hasher.write_u128(seed);
+        hasher.write_u8(salt);
+        hasher.finish()
+    });
+
+    format!("{high:016x}{low:016x}")
+}
+
+fn nanoseconds(time: SystemTime) -> u128 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
@@ -0,0 +1,99 @@
+use crate::timeline::{Index, Playhead, Timeline};
+
+use iced::time::{Instant, SystemTime};
+
+use std::fmt;
+
+/// The automatic advance of a paused playhead, following the original
+/// timing of the recorded events.
+#[derive(Debug, Clone, Copy)]
+pub struct Playback {
+    speed: Speed,
+    started: Instant,
+    origin: SystemTime,
+    index: Index,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Speed {
+    Half,
+    #[default]
+    Normal,
+    Double,
+    Quadruple,
+}
+
+impl Speed {
+    pub const ALL: &'static [Self] = &[Self::Half, Self::Normal, Self::Double, Self::Quadruple];
+
+    fn factor(self) -> f64 {
+        match self {
+            Speed::Half => 0.5,
+            Speed::Normal => 1.0,
+            Speed::Double => 2.0,
+            Speed::Quadruple => 4.0,
+        }
+    }
+}
+
+impl fmt::Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}×", self.factor())
+    }
+}
+
+impl Playback {
+    pub fn new(speed: Speed, timeline: &Timeline, index: Index, now: Instant) -> Option<Self> {
+        let origin = timeline.get(index)?.at();
+
+        Some(Self {
+            speed,
+            started: now,
+            origin,
+            index,
+        })
+    }
+
+    pub fn set_speed(&mut self, speed: Speed, timeline: &Timeline, now: Instant) {
+        if let Some(playback) = Self::new(speed, timeline, self.index, now) {
+            *self = playback;
+        }
+    }
+
+    /// Returns the playhead the playback has reached at `now`, if it moved.
+    ///
+    /// Playback restarts from the current `offset` if it got moved by
+    /// someone else, and ends by going live once the recorded events run
+    /// out.
+    pub fn advance(
+        &mut self,
+        timeline: &Timeline,
+        offset: Playhead,
+        now: Instant,
+    ) -> Option<Playhead> {
+        let Playhead::Paused(current) = offset else {
+            return None;
+        };
+
+        if current != self.index {
+            *self = Self::new(self.speed, timeline, current, now)?;
+        }
+
+        let elapsed = now
+            .duration_since(self.started)
+            .mul_f64(self.speed.factor());
+        let index = timeline.index_at(self.origin + elapsed);
+
+        if index >= timeline.end() {
+            return Some(Playhead::Live);
+        }
+
+        if index <= self.index {
+            return None;
+        }
+
+        self.index = index;
+
+        Some(Playhead::Paused(index))
+    }
+}
@@ -1,15 +1,32 @@
+mod boot;
+mod flamegraph;
 mod overview;
+mod palette;
 mod present;
-mod update;
+mod stats;
 
+pub mod board;
+pub mod compare;
 pub mod custom;
+pub mod messages;
+pub mod offenders;
+pub mod update;
 
+pub use board::Board;
+pub use boot::Boot;
+pub use compare::Compare;
 pub use custom::Custom;
+pub use flamegraph::Flamegraph;
+pub use messages::Messages;
+pub use offenders::Offenders;
 pub use overview::Overview;
+pub use palette::Palette;
 pub use present::Present;
+pub use stats::Stats;
 pub use update::Update;
 
 use crate::beacon::Event;
+use crate::timeline::Timeline;
 
 #[derive(Debug)]
 pub enum Screen {
@@ -17,6 +34,14 @@ pub enum Screen {
     Update(Update),
     Present(Present),
     Custom(Custom),
+    Flamegraph(Flamegraph),
+    Offenders(Offenders),
+    Stats(Stats),
+    Compare(Compare),
+    Boot(Boot),
+    Palette(Palette),
+    Messages(Messages),
+    Board(Board),
 }
 
 impl Screen {
@@ -34,6 +59,32 @@ impl Screen {
             Self::Custom(custom) => {
                 custom.invalidate();
             }
+            Self::Flamegraph(flamegraph) => {
+                flamegraph.invalidate();
+            }
+            Self::Offenders(_) | Self::Stats(_) | Self::Palette(_) | Self::Messages(_) => {}
+            Self::Compare(compare) => {
+                compare.invalidate();
+            }
+            Self::Boot(_) => {}
+            Self::Board(board) => {
+                board.invalidate();
+            }
+        }
+    }
+
+    /// Drops any state derived from the events of a cleared timeline.
+    pub fn reset(&mut self, timeline: &Timeline) {
+        match self {
+            Self::Boot(boot) => {
+                *boot = Boot::new(timeline);
+            }
+            Self::Messages(messages) => {
+                messages.reset();
+            }
+            _ => {
+                self.invalidate();
+            }
         }
     }
 
@@ -51,6 +102,19 @@ impl Screen {
             Self::Custom(custom) => {
                 custom.invalidate_by(event);
             }
+            Self::Flamegraph(flamegraph) => {
+                flamegraph.invalidate_by(event);
+            }
+            Self::Offenders(_) | Self::Stats(_) | Self::Palette(_) | Self::Messages(_) => {}
+            Self::Compare(compare) => {
+                compare.invalidate_by(event);
+            }
+            Self::Boot(boot) => {
+                boot.invalidate_by(event);
+            }
+            Self::Board(board) => {
+                board.invalidate_by(event);
+            }
         }
     }
 }
@@ -0,0 +1,732 @@
+use crate::beacon::span::present;
+use crate::beacon::{self, Span};
+use crate::chart;
+use crate::filter::Filter;
+use crate::keymap::{self, Keymap};
+use crate::timeline::{self, Timeline};
+use crate::widget::{self, diffused_text, tip};
+
+use iced::padding;
+use iced::widget::{
+    button, column, pane_grid, pick_list, row, scrollable, space, text, text_input, tooltip,
+};
+use iced::{Center, Element, Fill};
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// A dashboard of [`Module`]s laid out in a pane grid.
+#[derive(Debug)]
+pub struct Board {
+    panes: pane_grid::State<Pane>,
+    focus: Option<pane_grid::Pane>,
+}
+
+/// A [`Module`] along with the cache of its chart.
+#[derive(Debug)]
+pub struct Pane {
+    module: Module,
+    cache: chart::Cache,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Chart(chart::Interaction),
+    Dragged(pane_grid::DragEvent),
+    Resized(pane_grid::ResizeEvent),
+    Added(pane_grid::Pane, Module),
+    Replaced(pane_grid::Pane, Module),
+    Closed(pane_grid::Pane),
+    Clicked(pane_grid::Pane),
+    Maximized(pane_grid::Pane),
+    Restored,
+    Detached(pane_grid::Pane),
+    Selected(usize),
+    Created,
+    Renamed(String),
+    Deleted,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    ChartInteracted(chart::Interaction),
+    LayoutChanged,
+    Detached(Module),
+    Selected(usize),
+    Created,
+    Renamed(String),
+    Deleted,
+}
+
+/// Every board of the user, as they are persisted.
+///
+/// There is always at least one board.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Boards {
+    boards: Vec<Saved>,
+    /// The last board used is persisted in the [`Settings`] instead.
+    ///
+    /// [`Settings`]: crate::config::Settings
+    #[serde(skip)]
+    active: usize,
+}
+
+/// A named [`Layout`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Saved {
+    pub name: String,
+    pub layout: Layout,
+}
+
+impl Boards {
+    pub fn active(&self) -> &Saved {
+        &self.boards[self.active_index()]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Saved {
+        let active = self.active_index();
+
+        &mut self.boards[active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active.min(self.boards.len() - 1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Saved> {
+        self.boards.iter()
+    }
+
+    /// Returns whether the active board can be deleted; that is, whether
+    /// it is not the last one.
+    pub fn can_delete(&self) -> bool {
+        self.boards.len() > 1
+    }
+
+    /// Makes the board at the given position active; returns `false` if
+    /// there is no such board.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index < self.boards.len() {
+            self.active = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Adds a board with the default [`Layout`] and makes it active.
+    pub fn create(&mut self) {
+        self.boards.push(Saved {
+            name: format!("Board {}", self.boards.len() + 1),
+            layout: Layout::default(),
+        });
+
+        self.active = self.boards.len() - 1;
+    }
+
+    /// Deletes the active board, unless it is the last one.
+    pub fn delete(&mut self) {
+        if self.can_delete() {
+            let _ = self.boards.remove(self.active_index());
+            self.active = self.active.saturating_sub(1);
+        }
+    }
+
+    /// Returns whether the [`Boards`] hold at least one board, as they
+    /// always should unless the configuration was edited by hand.
+    pub fn is_valid(&self) -> bool {
+        !self.boards.is_empty()
+    }
+}
+
+impl Default for Boards {
+    fn default() -> Self {
+        Self {
+            boards: vec![Saved {
+                name: String::from("Default"),
+                layout: Layout::default(),
+            }],
+            active: 0,
+        }
+    }
+}
+
+/// The arrangement of the modules of a [`Board`], as it is persisted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Layout {
+    Split {
+        axis: Axis,
+        ratio: f32,
+        a: Box<Layout>,
+        b: Box<Layout>,
+    },
+    Pane(Module),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Default for Layout {
+    /// A big update chart on the left, with the present chart and the
+    /// rates on the right.
+    fn default() -> Self {
+        let pane = |module| Box::new(Layout::Pane(module));
+
+        Layout::Split {
+            axis: Axis::Vertical,
+            ratio: 0.6,
+            a: pane(Module::Performance(chart::Stage::Update)),
+            b: Box::new(Layout::Split {
+                axis: Axis::Horizontal,
+                ratio: 1.0 / 3.0,
+                a: pane(Module::Performance(chart::Stage::Present)),
+                b: Box::new(Layout::Split {
+                    axis: Axis::Horizontal,
+                    ratio: 0.5,
+                    a: pane(Module::FrameRate),
+                    b: pane(Module::MessageRate),
+                }),
+            }),
+        }
+    }
+}
+
+impl Pane {
+    pub fn new(module: Module) -> Self {
+        Self {
+            module,
+            cache: chart::Cache::default(),
+        }
+    }
+
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    pub fn invalidate(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn invalidate_by(&mut self, event: &beacon::Event) {
+        if matches!(event, beacon::Event::ThemeChanged { .. }) {
+            self.cache.clear();
+        } else if self.module.is_invalidated_by(event) {
+            self.cache.extend();
+        }
+    }
+
+    /// Lays out the chart of the [`Module`], without any decorations.
+    pub fn view<'a>(
+        &'a self,
+        context: chart::Context<'a>,
+        filter: Option<&'a Filter>,
+    ) -> Element<'a, chart::Interaction> {
+        let chart::Context {
+            timeline,
+            offset,
+            selection,
+            settings,
+            baseline,
+        } = context;
+
+        let cache = &self.cache;
+
+        match &self.module {
+            Module::Performance(stage) => chart::performance(
+                stage.clone(),
+                cache,
+                timeline,
+                offset,
+                selection,
+                settings,
+                baseline,
+            ),
+            Module::Frames => chart::frames(cache, timeline, offset, selection, settings.zoom),
+            Module::FrameRate => chart::frame_rate(cache, timeline, offset, selection, settings),
+            Module::MessageRate => {
+                chart::message_rate(cache, timeline, offset, selection, settings, filter)
+            }
+            Module::TasksSpawned => {
+                chart::tasks_spawned(cache, timeline, offset, selection, settings)
+            }
+            Module::SubscriptionsAlive => {
+                chart::subscriptions_alive(cache, timeline, offset, selection, settings)
+            }
+            Module::LayersRendered => {
+                chart::layers_rendered(cache, timeline, offset, selection, settings)
+            }
+            Module::Messages => messages(timeline, offset),
+        }
+    }
+}
+
+/// A chart that can be placed on a [`Board`].
+///
+/// Modules are persisted by name; see [`Module::key`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Module {
+    Performance(chart::Stage),
+    Frames,
+    FrameRate,
+    MessageRate,
+    TasksSpawned,
+    SubscriptionsAlive,
+    LayersRendered,
+    Messages,
+}
+
+impl Module {
+    /// Returns every [`Module`] available for the given [`Timeline`].
+    pub fn all(timeline: &Timeline) -> Vec<Self> {
+        let mut custom: Vec<_> = timeline.custom_stages().collect();
+        custom.sort_unstable();
+
+        [
+            chart::Stage::Update,
+            chart::Stage::View,
+            chart::Stage::Layout,
+            chart::Stage::Interact,
+            chart::Stage::Draw,
+            chart::Stage::Present,
+        ]
+        .into_iter()
+        .chain(
+            custom
+                .into_iter()
+                .map(|name| chart::Stage::Custom(name.to_owned())),
+        )
+        .map(Module::Performance)
+        .chain([
+            Module::Frames,
+            Module::FrameRate,
+            Module::MessageRate,
+            Module::TasksSpawned,
+            Module::SubscriptionsAlive,
+            Module::LayersRendered,
+            Module::Messages,
+        ])
+        .collect()
+    }
+
+    /// Returns the stable name of the [`Module`] in a [`Layout`]; custom
+    /// stages are prefixed with `custom:`.
+    fn key(&self) -> String {
+        match self {
+            Module::Performance(chart::Stage::Custom(name)) => format!("custom:{name}"),
+            Module::Performance(chart::Stage::Prepare(primitive)) => {
+                format!("prepare:{}", primitive_key(*primitive))
+            }
+            Module::Performance(chart::Stage::Render(primitive)) => {
+                format!("render:{}", primitive_key(*primitive))
+            }
+            Module::Performance(stage) => stage.to_string().to_lowercase(),
+            Module::Frames => String::from("frames"),
+            Module::FrameRate => String::from("frame_rate"),
+            Module::MessageRate => String::from("message_rate"),
+            Module::TasksSpawned => String::from("tasks_spawned"),
+            Module::SubscriptionsAlive => String::from("subscriptions_alive"),
+            Module::LayersRendered => String::from("layers_rendered"),
+            Module::Messages => String::from("messages"),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        if let Some(name) = key.strip_prefix("custom:") {
+            return Some(Module::Performance(chart::Stage::Custom(name.to_owned())));
+        }
+
+        if let Some(primitive) = key.strip_prefix("prepare:") {
+            return primitive_from_key(primitive)
+                .map(|primitive| Module::Performance(chart::Stage::Prepare(primitive)));
+        }
+
+        if let Some(primitive) = key.strip_prefix("render:") {
+            return primitive_from_key(primitive)
+                .map(|primitive| Module::Performance(chart::Stage::Render(primitive)));
+        }
+
+        Some(match key {
+            "update" => Module::Performance(chart::Stage::Update),
+            "view" => Module::Performance(chart::Stage::View),
+            "layout" => Module::Performance(chart::Stage::Layout),
+            "interact" => Module::Performance(chart::Stage::Interact),
+            "draw" => Module::Performance(chart::Stage::Draw),
+            "present" => Module::Performance(chart::Stage::Present),
+            "frames" => Module::Frames,
+            "frame_rate" => Module::FrameRate,
+            "message_rate" => Module::MessageRate,
+            "tasks_spawned" => Module::TasksSpawned,
+            "subscriptions_alive" => Module::SubscriptionsAlive,
+            "layers_rendered" => Module::LayersRendered,
+            "messages" => Module::Messages,
+            _ => return None,
+        })
+    }
+
+    fn is_invalidated_by(&self, event: &beacon::Event) -> bool {
+        match self {
+            Module::Performance(stage) => stage.duration(event).is_some(),
+            Module::Frames | Module::FrameRate | Module::LayersRendered => matches!(
+                event,
+                beacon::Event::SpanFinished {
+                    span: Span::Present { .. },
+                    ..
+                }
+            ),
+            Module::Messages => false,
+            Module::MessageRate | Module::TasksSpawned | Module::SubscriptionsAlive => matches!(
+                event,
+                beacon::Event::SpanFinished {
+                    span: Span::Update { .. },
+                    ..
+                }
+            ),
+        }
+    }
+}
+
+impl From<Module> for String {
+    fn from(module: Module) -> Self {
+        module.key()
+    }
+}
+
+impl TryFrom<String> for Module {
+    type Error = String;
+
+    fn try_from(key: String) -> Result<Self, Self::Error> {
+        Module::from_key(&key).ok_or_else(|| format!("unknown module: {key}"))
+    }
+}
+
+const PRIMITIVES: [(present::Primitive, &str); 5] = [
+    (present::Primitive::Quad, "quad"),
+    (present::Primitive::Triangle, "triangle"),
+    (present::Primitive::Shader, "shader"),
+    (present::Primitive::Image, "image"),
+    (present::Primitive::Text, "text"),
+];
+
+fn primitive_key(primitive: present::Primitive) -> &'static str {
+    PRIMITIVES
+        .iter()
+        .find(|(candidate, _)| *candidate == primitive)
+        .map(|(_, key)| *key)
+        .unwrap_or_default()
+}
+
+fn primitive_from_key(key: &str) -> Option<present::Primitive> {
+    PRIMITIVES
+        .iter()
+        .find(|(_, candidate)| *candidate == key)
+        .map(|(primitive, _)| *primitive)
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Module::Performance(stage) => stage.fmt(f),
+            Module::Frames => f.write_str("Frames"),
+            Module::FrameRate => f.write_str("FPS"),
+            Module::MessageRate => f.write_str("Message Rate"),
+            Module::TasksSpawned => f.write_str("Tasks Spawned"),
+            Module::SubscriptionsAlive => f.write_str("Subscriptions Alive"),
+            Module::LayersRendered => f.write_str("Layers Rendered"),
+            Module::Messages => f.write_str("Messages"),
+        }
+    }
+}
+
+impl Board {
+    pub fn new(layout: &Layout) -> Self {
+        fn configuration(layout: &Layout) -> pane_grid::Configuration<Pane> {
+            match layout {
+                Layout::Split { axis, ratio, a, b } => pane_grid::Configuration::Split {
+                    axis: match axis {
+                        Axis::Horizontal => pane_grid::Axis::Horizontal,
+                        Axis::Vertical => pane_grid::Axis::Vertical,
+                    },
+                    ratio: *ratio,
+                    a: Box::new(configuration(a)),
+                    b: Box::new(configuration(b)),
+                },
+                Layout::Pane(module) => pane_grid::Configuration::Pane(Pane::new(module.clone())),
+            }
+        }
+
+        Self {
+            panes: pane_grid::State::with_configuration(configuration(layout)),
+            focus: None,
+        }
+    }
+
+    /// Returns the current [`Layout`] of the [`Board`].
+    pub fn layout(&self) -> Layout {
+        fn layout(node: &pane_grid::Node, panes: &pane_grid::State<Pane>) -> Layout {
+            match node {
+                pane_grid::Node::Split {
+                    axis, ratio, a, b, ..
+                } => Layout::Split {
+                    axis: match axis {
+                        pane_grid::Axis::Horizontal => Axis::Horizontal,
+                        pane_grid::Axis::Vertical => Axis::Vertical,
+                    },
+                    ratio: *ratio,
+                    a: Box::new(layout(a, panes)),
+                    b: Box::new(layout(b, panes)),
+                },
+                pane_grid::Node::Pane(pane) => {
+                    Layout::Pane(panes.get(*pane).expect("pane in layout").module.clone())
+                }
+            }
+        }
+
+        layout(self.panes.layout(), &self.panes)
+    }
+
+    pub fn invalidate(&mut self) {
+        for (_, pane) in self.panes.iter_mut() {
+            pane.invalidate();
+        }
+    }
+
+    pub fn invalidate_by(&mut self, event: &beacon::Event) {
+        for (_, pane) in self.panes.iter_mut() {
+            pane.invalidate_by(event);
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::Chart(interaction) => Some(Event::ChartInteracted(interaction)),
+            Message::Dragged(pane_grid::DragEvent::Dropped { pane, target }) => {
+                self.panes.drop(pane, target);
+
+                Some(Event::LayoutChanged)
+            }
+            Message::Dragged(_) => None,
+            Message::Resized(pane_grid::ResizeEvent { split, ratio }) => {
+                self.panes.resize(split, ratio);
+
+                Some(Event::LayoutChanged)
+            }
+            Message::Added(pane, module) => {
+                self.panes.restore();
+
+                let _ = self
+                    .panes
+                    .split(pane_grid::Axis::Horizontal, pane, Pane::new(module));
+
+                Some(Event::LayoutChanged)
+            }
+            Message::Replaced(pane, module) => {
+                if let Some(pane) = self.panes.get_mut(pane) {
+                    *pane = Pane::new(module);
+                }
+
+                Some(Event::LayoutChanged)
+            }
+            Message::Closed(pane) => {
+                if self.panes.len() > 1 {
+                    self.panes.restore();
+                    let _ = self.panes.close(pane);
+                }
+
+                Some(Event::LayoutChanged)
+            }
+            Message::Clicked(pane) => {
+                self.focus = Some(pane);
+
+                None
+            }
+            Message::Maximized(pane) => {
+                self.focus = Some(pane);
+                self.panes.maximize(pane);
+
+                None
+            }
+            Message::Restored => {
+                self.panes.restore();
+
+                None
+            }
+            Message::Detached(pane) => self
+                .panes
+                .get(pane)
+                .map(|pane| Event::Detached(pane.module.clone())),
+            Message::Selected(index) => Some(Event::Selected(index)),
+            Message::Created => Some(Event::Created),
+            Message::Renamed(name) => Some(Event::Renamed(name)),
+            Message::Deleted => Some(Event::Deleted),
+        }
+    }
+
+    /// Maximizes the focused pane, or restores the layout if a pane is
+    /// already maximized.
+    pub fn toggle_maximized(&mut self) {
+        if self.panes.maximized().is_some() {
+            self.panes.restore();
+        } else if let Some(pane) = self
+            .focus
+            .filter(|pane| self.panes.get(*pane).is_some())
+            .or_else(|| self.panes.iter().next().map(|(pane, _)| *pane))
+        {
+            self.panes.maximize(pane);
+        }
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        context: chart::Context<'a>,
+        filter: Option<&'a Filter>,
+        boards: &'a Boards,
+        keymap: &'a Keymap,
+    ) -> Element<'a, Message> {
+        let modules = Module::all(context.timeline);
+        let is_closable = self.panes.len() > 1;
+
+        let tabs = row(boards.iter().enumerate().map(|(i, board)| {
+            let tab = button(text(&board.name).size(12))
+                .padding([2, 5])
+                .on_press(Message::Selected(i))
+                .style(if i == boards.active_index() {
+                    button::secondary
+                } else {
+                    button::text
+                });
+
+            match keymap.binding(keymap::Action::SwitchBoard(i)) {
+                Some(binding) => tip(
+                    tab,
+                    format!("Switch board ({binding})"),
+                    tooltip::Position::Bottom,
+                ),
+                None => tab.into(),
+            }
+        }))
+        .spacing(5);
+
+        let name = text_input("Name", &boards.active().name)
+            .on_input(Message::Renamed)
+            .size(12)
+            .padding([2, 5])
+            .width(150);
+
+        let create = button(text("New").size(12))
+            .padding([2, 5])
+            .on_press(Message::Created)
+            .style(button::text);
+
+        let delete = button(text("Delete").size(12))
+            .padding([2, 5])
+            .on_press_maybe(boards.can_delete().then_some(Message::Deleted))
+            .style(button::text);
+
+        let header = row![tabs, space::horizontal(), name, create, delete]
+            .spacing(10)
+            .align_y(Center);
+
+        let maximize_tip = keymap.hint("Maximize or restore pane", keymap::Action::ToggleMaximized);
+
+        let panes = pane_grid(&self.panes, move |id, pane, is_maximized| {
+            let chart = pane.view(context, filter);
+
+            let export = match &pane.module {
+                Module::Performance(stage) => chart::export(stage.clone()).map(Message::Chart),
+                _ => space().into(),
+            };
+
+            let add = pick_list(modules.clone(), None::<Module>, move |module| {
+                Message::Added(id, module)
+            })
+            .placeholder("Add")
+            .text_size(10)
+            .padding([0, 5]);
+
+            let replace = pick_list(modules.clone(), None::<Module>, move |module| {
+                Message::Replaced(id, module)
+            })
+            .placeholder("Replace")
+            .text_size(10)
+            .padding([0, 5]);
+
+            let close = button(text("Close").size(10))
+                .padding([0, 5])
+                .on_press_maybe(is_closable.then_some(Message::Closed(id)))
+                .style(button::text);
+
+            let maximize = tip(
+                button(text(if is_maximized { "Restore" } else { "Maximize" }).size(10))
+                    .padding([0, 5])
+                    .on_press(if is_maximized {
+                        Message::Restored
+                    } else {
+                        Message::Maximized(id)
+                    })
+                    .style(button::text),
+                maximize_tip.clone(),
+                tooltip::Position::Bottom,
+            );
+
+            let detach = tip(
+                button(text("Detach").size(10))
+                    .padding([0, 5])
+                    .on_press(Message::Detached(id))
+                    .style(button::text),
+                "Pop out in its own window",
+                tooltip::Position::Bottom,
+            );
+
+            let controls = row![export, add, replace, maximize, detach, close]
+                .spacing(5)
+                .align_y(Center);
+
+            // The title bar is where panes are dragged from
+            let title_bar = pane_grid::TitleBar::new(diffused_text(pane.module.to_string()))
+                .controls(controls)
+                .padding(padding::all(10).bottom(5));
+
+            pane_grid::Content::new(chart.map(Message::Chart))
+                .title_bar(title_bar)
+                .style(widget::card_style)
+        })
+        .width(Fill)
+        .height(Fill)
+        .spacing(10)
+        .on_click(Message::Clicked)
+        .on_drag(Message::Dragged)
+        .on_resize(10, Message::Resized);
+
+        column![header, panes].spacing(10).into()
+    }
+}
+
+/// Lists the latest update messages up to the playhead.
+fn messages<'a>(
+    timeline: &'a Timeline,
+    offset: timeline::Playhead,
+) -> Element<'a, chart::Interaction> {
+    const MAX_MESSAGES: usize = 100;
+
+    let messages = timeline.updates(offset).take(MAX_MESSAGES).map(|update| {
+        button(text!("#{} {}", update.number, update.message).size(10))
+            .width(Fill)
+            .padding([2, 5])
+            .on_press(chart::Interaction::Pinned(update.index))
+            .style(button::text)
+            .into()
+    });
+
+    scrollable(column(messages).padding([0, 5]))
+        .width(Fill)
+        .height(Fill)
+        .into()
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn boards_round_trip() {
+    let mut boards = Boards::default();
+
+    boards.create();
+    boards.active_mut().layout = Layout::Split {
+        axis: Axis::Horizontal,
+        ratio: 0.25,
+        a: Box::new(Layout::default()),
+        b: Box::new(Layout::Split {
+            axis: Axis::Vertical,
+            ratio: 0.5,
+            a: Box::new(Layout::Pane(Module::Performance(chart::Stage::Custom(
+                String::from("custom:parse"),
+            )))),
+            b: Box::new(Layout::Pane(Module::Performance(chart::Stage::Render(
+                present::Primitive::Text,
+            )))),
+        }),
+    };
+
+    let contents = toml::to_string_pretty(&boards).expect("serialize boards");
+    let mut loaded: Boards = toml::from_str(&contents).expect("deserialize boards");
+
+    assert!(loaded.select(1));
+    assert_eq!(loaded, boards);
+}
+
+#[test]
+fn unknown_modules_are_rejected() {
+    let saved = |module: &str| {
+        toml::from_str::<Saved>(&format!(
+            "name = \"GPU\"\nlayout = {{ Pane = \"{module}\" }}"
+        ))
+    };
+
+    assert!(saved("gpu").is_err());
+    assert_eq!(
+        saved("frame_rate").expect("known module").layout,
+        Layout::Pane(Module::FrameRate)
+    );
+}
+
+#[test]
+fn the_last_board_is_never_deleted() {
+    let mut boards = Boards::default();
+
+    boards.create();
+    boards.active_mut().name = String::from("GPU");
+
+    assert_eq!(boards.active_index(), 1);
+
+    boards.delete();
+
+    assert_eq!(boards.active().name, "Default");
+
+    boards.delete();
+
+    assert!(boards.is_valid());
+    assert!(!boards.select(1));
+}
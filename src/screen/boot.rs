@@ -0,0 +1,136 @@
+use crate::beacon::{Event, Span};
+use crate::stats;
+use crate::timeline::Timeline;
+use crate::widget::card;
+
+use iced::time::{Duration, SystemTime};
+use iced::widget::{column, container, row, scrollable, space, text};
+use iced::{Background, Center, Element, Fill, FillPortion, Theme};
+
+/// The startup of the application: its [`Span::Boot`] followed by every
+/// span until its first [`Span::Present`], laid out as a waterfall.
+#[derive(Debug, Default)]
+pub struct Boot {
+    phases: Vec<Phase>,
+    is_complete: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Phase {
+    name: String,
+    start: SystemTime,
+    duration: Duration,
+}
+
+impl Boot {
+    pub fn new(timeline: &Timeline) -> Self {
+        let mut boot = Self::default();
+
+        for event in timeline.events(*timeline.range().start()) {
+            boot.push(event);
+        }
+
+        boot
+    }
+
+    pub fn invalidate_by(&mut self, event: &Event) {
+        self.push(event);
+    }
+
+    fn push(&mut self, event: &Event) {
+        let Event::SpanFinished { at, duration, span } = event else {
+            return;
+        };
+
+        let phase = Phase {
+            name: stats::stage(span).to_owned(),
+            start: at.checked_sub(*duration).unwrap_or(*at),
+            duration: *duration,
+        };
+
+        match span {
+            Span::Boot => {
+                self.phases = vec![phase];
+                self.is_complete = false;
+            }
+            _ if self.phases.is_empty() || self.is_complete => {}
+            span => {
+                self.is_complete = matches!(span, Span::Present { .. });
+                self.phases.push(phase);
+            }
+        }
+    }
+
+    pub fn view<'a, Message: 'a>(&'a self) -> Element<'a, Message> {
+        const PRECISION: f64 = 1_000.0;
+
+        let Some(start) = self.phases.iter().map(|phase| phase.start).min() else {
+            return card(
+                "Boot",
+                container(text("No boot has been recorded yet.").size(12)).padding(10),
+            );
+        };
+
+        let end = self
+            .phases
+            .iter()
+            .map(|phase| phase.start + phase.duration)
+            .max()
+            .unwrap_or(start);
+
+        let total = end.duration_since(start).unwrap_or_default();
+
+        let portion = |duration: Duration| {
+            if total.is_zero() {
+                return 0;
+            }
+
+            (duration.as_secs_f64() / total.as_secs_f64() * PRECISION).round() as u16
+        };
+
+        let phases = self.phases.iter().map(|phase| {
+            let offset = phase.start.duration_since(start).unwrap_or_default();
+            let length = portion(phase.duration).max(1);
+            let before = portion(offset);
+            let after = (PRECISION as u16).saturating_sub(before + length);
+
+            let bar = container(space())
+                .width(FillPortion(length))
+                .height(12)
+                .style(|theme: &Theme| container::Style {
+                    background: Some(Background::from(theme.palette().primary.base.color)),
+                    ..container::Style::default()
+                });
+
+            row![
+                text(&phase.name).size(12).width(100),
+                row![
+                    space().width(FillPortion(before)),
+                    bar,
+                    space().width(FillPortion(after)),
+                ]
+                .width(Fill),
+                text!("{:?}", phase.duration).size(12).width(100),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        });
+
+        let title = if self.is_complete {
+            format!("Boot to first frame ({total:?})")
+        } else {
+            format!("Boot ({total:?} so far)")
+        };
+
+        card(
+            title,
+            container(
+                scrollable(column(phases).spacing(5))
+                    .width(Fill)
+                    .height(Fill),
+            )
+            .padding(10),
+        )
+    }
+}
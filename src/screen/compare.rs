@@ -0,0 +1,160 @@
+use crate::beacon::Event;
+use crate::chart;
+use crate::session;
+use crate::stats::{self, Statistics};
+use crate::timeline::{Playhead, Timeline};
+use crate::widget::card;
+
+use iced::Element;
+use iced::time::Duration;
+use iced::widget::{column, row};
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const STAGES: [chart::Stage; 6] = [
+    chart::Stage::Update,
+    chart::Stage::View,
+    chart::Stage::Layout,
+    chart::Stage::Interact,
+    chart::Stage::Draw,
+    chart::Stage::Present,
+];
+
+/// Two saved sessions loaded side by side, usually before and after some
+/// performance work.
+#[derive(Debug)]
+pub struct Comparison {
+    before: Recording,
+    after: Recording,
+}
+
+#[derive(Debug)]
+struct Recording {
+    name: String,
+    timeline: Timeline,
+    statistics: BTreeMap<String, Statistics>,
+}
+
+impl Recording {
+    fn new(path: &Path, entries: Vec<session::Entry>) -> Self {
+        let mut timeline = Timeline::new();
+        let mut collector = stats::Collector::new();
+
+        for event in entries.into_iter().filter_map(session::Entry::into_event) {
+            collector.push(&event);
+            timeline.push(event);
+        }
+
+        Self {
+            name: path
+                .file_stem()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            timeline,
+            statistics: collector.statistics(),
+        }
+    }
+}
+
+impl Comparison {
+    pub fn new(before: (&Path, Vec<session::Entry>), after: (&Path, Vec<session::Entry>)) -> Self {
+        Self {
+            before: Recording::new(before.0, before.1),
+            after: Recording::new(after.0, after.1),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        format!("{} vs {}", self.before.name, self.after.name)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Compare {
+    before: [chart::Cache; 6],
+    after: [chart::Cache; 6],
+}
+
+impl Compare {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn invalidate(&mut self) {
+        for cache in self.before.iter().chain(&self.after) {
+            cache.clear();
+        }
+    }
+
+    pub fn invalidate_by(&mut self, event: &Event) {
+        if let Event::ThemeChanged { .. } = event {
+            self.invalidate();
+        }
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        comparison: &'a Comparison,
+        settings: chart::Settings,
+    ) -> Element<'a, chart::Interaction> {
+        let rows = STAGES
+            .into_iter()
+            .zip(self.before.iter().zip(&self.after))
+            .map(|(stage, (before, after))| {
+                let performance = |recording: &'a Recording, cache: &'a chart::Cache| {
+                    chart::performance(
+                        stage.clone(),
+                        cache,
+                        &recording.timeline,
+                        Playhead::Live,
+                        Playhead::Live,
+                        settings,
+                        &chart::Baseline::default(),
+                    )
+                };
+
+                let name = stage.to_string();
+
+                let change = comparison
+                    .before
+                    .statistics
+                    .get(&name)
+                    .zip(comparison.after.statistics.get(&name))
+                    .map(|(before, after)| {
+                        format!(
+                            " ({} p95, {} avg)",
+                            delta(before.p95, after.p95),
+                            delta(before.mean, after.mean)
+                        )
+                    })
+                    .unwrap_or_default();
+
+                row![
+                    card(
+                        format!("{name} - {}", comparison.before.name),
+                        performance(&comparison.before, before)
+                    ),
+                    card(
+                        format!("{name} - {}{change}", comparison.after.name),
+                        performance(&comparison.after, after)
+                    ),
+                ]
+                .spacing(10)
+                .into()
+            });
+
+        column(rows).spacing(10).into()
+    }
+}
+
+/// Formats the relative change from `before` to `after`, like `+18%`.
+fn delta(before: Duration, after: Duration) -> String {
+    if before.is_zero() {
+        return String::from("n/a");
+    }
+
+    let change = (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+
+    format!("{change:+.0}%")
+}
@@ -64,7 +64,7 @@ impl Custom {
                 span: span::Span::Custom { name },
                 ..
             } => {
-                self.timings.entry(name.to_owned()).or_default().clear();
+                self.timings.entry(name.to_owned()).or_default().extend();
             }
             beacon::Event::ThemeChanged { .. } => {
                 self.invalidate();
@@ -103,7 +103,8 @@ impl Custom {
         timeline: &'a Timeline,
         offset: timeline::Playhead,
         selection: timeline::Playhead,
-        zoom: chart::Zoom,
+        settings: chart::Settings,
+        baseline: &'a chart::Baseline,
     ) -> Element<'a, Message> {
         if self.timings.is_empty() {
             let code = |text| {
@@ -138,18 +139,15 @@ impl Custom {
         }
 
         let charts = self.timings.iter().map(|(name, cache)| {
-            card(
-                name,
+            let stage = chart::Stage::Custom(name.to_owned());
+
+            chart::card(
+                stage.clone(),
                 chart::performance(
-                    chart::Stage::Custom(name.to_owned()),
-                    cache,
-                    timeline,
-                    offset,
-                    selection,
-                    zoom,
-                )
-                .map(Message::Chart),
+                    stage, cache, timeline, offset, selection, settings, baseline,
+                ),
             )
+            .map(Message::Chart)
         });
 
         column(charts).spacing(10).into()
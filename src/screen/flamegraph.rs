@@ -0,0 +1,293 @@
+use crate::beacon::{Event, Span};
+use crate::chart;
+use crate::stats;
+use crate::timeline::{self, Timeline};
+use crate::widget::card;
+
+use iced::mouse;
+use iced::time::{Duration, SystemTime};
+use iced::widget::canvas;
+use iced::window;
+use iced::{
+    Color, Element, Fill, Font, Pixels, Point, Rectangle, Renderer, Size, Theme, Top, theme,
+};
+
+#[derive(Debug, Default)]
+pub struct Flamegraph {
+    cache: chart::Cache,
+}
+
+impl Flamegraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn invalidate(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn invalidate_by(&mut self, event: &Event) {
+        match event {
+            Event::SpanFinished { .. } | Event::ThemeChanged { .. } => {
+                self.cache.clear();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        timeline: &'a Timeline,
+        _offset: timeline::Playhead,
+        selection: timeline::Playhead,
+        _settings: chart::Settings,
+    ) -> Element<'a, chart::Interaction> {
+        let blocks = frame(timeline, selection);
+
+        let duration = blocks
+            .iter()
+            .map(|block| block.start + block.duration)
+            .max()
+            .unwrap_or_default();
+
+        card(
+            format!("Frame ({duration:?})"),
+            canvas(Graph {
+                blocks,
+                duration,
+                cache: &self.cache,
+            })
+            .width(Fill)
+            .height(Fill),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Block {
+    index: timeline::Index,
+    span: Span,
+    start: Duration,
+    duration: Duration,
+    depth: usize,
+}
+
+/// Collects the spans of the frame at the given playhead; that is, every
+/// span since the [`Span::Present`] that finished the previous frame.
+fn frame(timeline: &Timeline, playhead: timeline::Playhead) -> Vec<Block> {
+    const MAX_SPANS: usize = 1_000;
+
+    let mut spans: Vec<(timeline::Index, SystemTime, Duration, &Span)> = Vec::new();
+
+    for (index, event) in timeline.seek_with_index(playhead) {
+        let Event::SpanFinished { at, duration, span } = event else {
+            continue;
+        };
+
+        if matches!(span, Span::Present { .. }) && !spans.is_empty() {
+            break;
+        }
+
+        spans.push((
+            index,
+            at.checked_sub(*duration).unwrap_or(*at),
+            *duration,
+            span,
+        ));
+
+        if spans.len() >= MAX_SPANS {
+            break;
+        }
+    }
+
+    let Some(frame_start) = spans.iter().map(|(_, start, _, _)| *start).min() else {
+        return Vec::new();
+    };
+
+    spans.sort_by(|(_, a_start, a_duration, _), (_, b_start, b_duration, _)| {
+        a_start.cmp(b_start).then(b_duration.cmp(a_duration))
+    });
+
+    let mut stack: Vec<SystemTime> = Vec::new();
+
+    spans
+        .into_iter()
+        .map(|(index, start, duration, span)| {
+            while stack.last().is_some_and(|end| *end <= start) {
+                let _ = stack.pop();
+            }
+
+            let depth = stack.len();
+            stack.push(start + duration);
+
+            Block {
+                index,
+                span: span.clone(),
+                start: start.duration_since(frame_start).unwrap_or_default(),
+                duration,
+                depth,
+            }
+        })
+        .collect()
+}
+
+struct Graph<'a> {
+    blocks: Vec<Block>,
+    duration: Duration,
+    cache: &'a chart::Cache,
+}
+
+impl Graph<'_> {
+    const ROW_HEIGHT: f32 = 20.0;
+    const CHARACTER_WIDTH: f32 = 6.0;
+
+    fn bounds(&self, block: &Block, size: Size) -> Rectangle {
+        let scale = size.width / self.duration.as_secs_f32().max(f32::EPSILON);
+
+        Rectangle {
+            x: block.start.as_secs_f32() * scale,
+            y: size.height - (block.depth + 1) as f32 * Self::ROW_HEIGHT,
+            width: (block.duration.as_secs_f32() * scale).max(1.0),
+            height: Self::ROW_HEIGHT - 1.0,
+        }
+    }
+
+    fn block_at(&self, position: Point, size: Size) -> Option<&Block> {
+        self.blocks
+            .iter()
+            .rev()
+            .find(|block| self.bounds(block, size).contains(position))
+    }
+}
+
+impl canvas::Program<chart::Interaction> for Graph<'_> {
+    type State = Option<timeline::Index>;
+
+    fn update(
+        &self,
+        hovered: &mut Option<timeline::Index>,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<chart::Interaction>> {
+        if let iced::Event::Window(window::Event::RedrawRequested(_)) = event {
+            return self
+                .cache
+                .redraw_at()
+                .map(canvas::Action::request_redraw_at);
+        }
+
+        let iced::Event::Mouse(event) = event else {
+            return None;
+        };
+
+        let block = cursor
+            .position_in(bounds)
+            .and_then(|position| self.block_at(position, bounds.size()));
+
+        match event {
+            mouse::Event::CursorMoved { .. } | mouse::Event::CursorLeft => {
+                let index = block.map(|block| block.index);
+
+                if *hovered != index {
+                    *hovered = index;
+
+                    return Some(canvas::Action::request_redraw());
+                }
+
+                None
+            }
+            mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                let block = block?;
+
+                Some(
+                    canvas::Action::publish(chart::Interaction::Selected(block.index))
+                        .and_capture(),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        hovered: &Option<timeline::Index>,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let palette = theme.palette();
+
+        let blocks = self.cache.draw(renderer, bounds.size(), |frame| {
+            for block in &self.blocks {
+                let bounds = self.bounds(block, frame.size());
+
+                frame.fill_rectangle(
+                    bounds.position(),
+                    bounds.size(),
+                    color(&block.span, palette),
+                );
+
+                if bounds.width > 40.0 {
+                    let label = format!("{} {:?}", stats::stage(&block.span), block.duration);
+                    let fitting = ((bounds.width - 6.0) / Self::CHARACTER_WIDTH) as usize;
+
+                    frame.fill_text(canvas::Text {
+                        content: label.chars().take(fitting).collect(),
+                        position: Point::new(bounds.x + 3.0, bounds.y + 4.0),
+                        color: palette.background.base.text,
+                        size: Pixels(10.0),
+                        font: Font::MONOSPACE,
+                        align_y: Top,
+                        ..canvas::Text::default()
+                    });
+                }
+            }
+        });
+
+        let mut overlay = canvas::Frame::new(renderer, bounds.size());
+
+        if let Some(block) = hovered
+            .as_ref()
+            .and_then(|index| self.blocks.iter().find(|block| block.index == *index))
+        {
+            let bounds = self.bounds(block, overlay.size());
+
+            overlay.fill_rectangle(
+                bounds.position(),
+                bounds.size(),
+                Color::WHITE.scale_alpha(0.2),
+            );
+
+            overlay.fill_text(canvas::Text {
+                content: format!(
+                    "{} {:?} (+{:?})",
+                    stats::stage(&block.span),
+                    block.duration,
+                    block.start
+                ),
+                position: Point::new(5.0, 5.0),
+                color: palette.background.base.text,
+                size: Pixels(12.0),
+                font: Font::MONOSPACE,
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![blocks, overlay.into_geometry()]
+    }
+}
+
+fn color(span: &Span, palette: &theme::Palette) -> Color {
+    match span {
+        Span::Boot => palette.background.strong.color,
+        Span::Update { .. } => palette.primary.base.color,
+        Span::View { .. } => palette.success.base.color,
+        Span::Layout { .. } => palette.success.strong.color,
+        Span::Interact { .. } => palette.secondary.base.color,
+        Span::Draw { .. } => palette.danger.weak.color,
+        Span::Present { .. } => palette.primary.weak.color,
+        Span::Custom { .. } => palette.background.strong.color,
+    }
+}
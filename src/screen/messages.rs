@@ -0,0 +1,609 @@
+use crate::chart;
+use crate::filter::{self, Filter};
+use crate::timeline::{self, Timeline};
+use crate::widget::card;
+
+use iced::time::Duration;
+use iced::widget::{button, column, container, row, scrollable, space, text, text_input, toggler};
+use iced::{Center, Element, Fill, FillPortion, Theme};
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::iter;
+
+mod payload;
+
+/// Every update message in the buffer before the playhead, newest first.
+///
+/// Clicking a message pins the playhead at its update, rewinding the
+/// application if it can time travel.
+///
+/// Each message shows when it was handled and how long its update took,
+/// with slow updates highlighted against the frame budget.
+///
+/// Breakpoints pause the playhead as soon as a matching message is
+/// received, like a debugger would.
+///
+/// Consecutive messages of the same variant are collapsed into a single
+/// row (e.g. `Tick ×240`) that can be expanded.
+///
+/// Only the rows inside the viewport are laid out, so the log stays
+/// responsive no matter how many messages have been recorded.
+#[derive(Debug, Default)]
+pub struct Messages {
+    search: String,
+    anchor: Option<timeline::Index>,
+    scroll: f32,
+    height: f32,
+    show_repeats: bool,
+    expanded: HashSet<timeline::Index>,
+    collapsed: HashSet<payload::Path>,
+    breakpoint: String,
+    /// Bumped whenever the entries of the log need to be rebuilt.
+    revision: usize,
+    log: RefCell<Log>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SearchChanged(String),
+    FilterChanged(String),
+    ToggleFilterRate(bool),
+    ToggleFollow,
+    Scrolled(scrollable::Viewport),
+    Pin(timeline::Index),
+    ToggleRepeats,
+    Expand(timeline::Index),
+    Copy,
+    Export,
+    TogglePayload(payload::Path),
+    BreakpointChanged(String),
+    AddBreakpoint,
+    RemoveBreakpoint(usize),
+    ToggleRewind(bool),
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    FilterChanged(Filter),
+    FilterRateToggled(bool),
+    Pinned(timeline::Index),
+    CopyRequested,
+    ExportRequested,
+    BreakpointAdded(Filter),
+    BreakpointRemoved(usize),
+    RewindToggled(bool),
+}
+
+impl Messages {
+    const ROW_HEIGHT: f32 = 20.0;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets everything tied to recorded messages, going back to
+    /// following the playhead.
+    pub fn reset(&mut self) {
+        self.anchor = None;
+        self.scroll = 0.0;
+        self.expanded.clear();
+        self.collapsed.clear();
+        self.revision += 1;
+    }
+
+    /// Whether the log follows the playhead; that is, whether new messages
+    /// show up at the top as they arrive.
+    pub fn is_following(&self) -> bool {
+        self.anchor.is_none()
+    }
+
+    pub fn update(&mut self, message: Message, playhead: timeline::Index) -> Option<Event> {
+        match message {
+            Message::SearchChanged(search) => {
+                self.search = search;
+                self.revision += 1;
+            }
+            Message::FilterChanged(filter) => {
+                return Some(Event::FilterChanged(Filter::new(filter)));
+            }
+            Message::ToggleFilterRate(is_enabled) => {
+                return Some(Event::FilterRateToggled(is_enabled));
+            }
+            Message::Pin(index) => {
+                return Some(Event::Pinned(index));
+            }
+            Message::BreakpointChanged(breakpoint) => {
+                self.breakpoint = breakpoint;
+            }
+            Message::AddBreakpoint => {
+                let breakpoint = Filter::new(std::mem::take(&mut self.breakpoint));
+
+                if !breakpoint.is_empty() {
+                    return Some(Event::BreakpointAdded(breakpoint));
+                }
+            }
+            Message::RemoveBreakpoint(index) => {
+                return Some(Event::BreakpointRemoved(index));
+            }
+            Message::ToggleRewind(is_enabled) => {
+                return Some(Event::RewindToggled(is_enabled));
+            }
+            Message::TogglePayload(path) => {
+                if !self.collapsed.remove(&path) {
+                    let _ = self.collapsed.insert(path);
+                }
+            }
+            Message::Copy => {
+                return Some(Event::CopyRequested);
+            }
+            Message::Export => {
+                return Some(Event::ExportRequested);
+            }
+            Message::ToggleRepeats => {
+                self.show_repeats = !self.show_repeats;
+                self.revision += 1;
+            }
+            Message::Expand(index) => {
+                if !self.expanded.remove(&index) {
+                    let _ = self.expanded.insert(index);
+                }
+
+                self.revision += 1;
+            }
+            Message::ToggleFollow => {
+                self.anchor = if self.is_following() {
+                    Some(playhead)
+                } else {
+                    None
+                };
+            }
+            Message::Scrolled(viewport) => {
+                self.scroll = viewport.absolute_offset().y;
+                self.height = viewport.bounds().height;
+
+                // Scrolling away from the newest message stops following,
+                // as otherwise the rows would move under the cursor.
+                if self.scroll > 0.0 && self.is_following() {
+                    self.anchor = Some(playhead);
+                } else if self.scroll == 0.0 {
+                    self.anchor = None;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the logged messages as plain text, one per line, exactly as
+    /// they are filtered in the log.
+    pub fn to_text(
+        &self,
+        timeline: &Timeline,
+        offset: timeline::Playhead,
+        filter: &Filter,
+    ) -> String {
+        self.updates(timeline, offset, filter)
+            .map(|update| {
+                let datetime: chrono::DateTime<chrono::Local> = update.at.into();
+
+                format!(
+                    "#{} {} {:?} {}\n",
+                    update.number,
+                    datetime.format("%H:%M:%S%.3f"),
+                    update.duration,
+                    update.message
+                )
+            })
+            .collect()
+    }
+
+    fn playhead(&self, timeline: &Timeline, offset: timeline::Playhead) -> timeline::Index {
+        match self.anchor {
+            Some(anchor) => anchor.min(timeline.index(offset)),
+            None => timeline.index(offset),
+        }
+    }
+
+    fn updates<'a>(
+        &self,
+        timeline: &'a Timeline,
+        offset: timeline::Playhead,
+        filter: &'a Filter,
+    ) -> impl Iterator<Item = &'a timeline::Update> + Clone + 'a {
+        let search = self.search.to_lowercase();
+
+        timeline
+            .updates(self.playhead(timeline, offset))
+            .filter(|update| filter.matches(&update.message))
+            .filter(move |update| {
+                search.is_empty() || update.message.to_lowercase().contains(&search)
+            })
+    }
+
+    /// Brings the cached entries of the log up to date with the timeline.
+    ///
+    /// Entries are only rebuilt from scratch when the filters change or
+    /// the playhead moves back; otherwise, new updates are merged into the
+    /// newest entries and evicted ones are dropped.
+    fn sync(&self, timeline: &Timeline, offset: timeline::Playhead, filter: &Filter) {
+        let mut log = self.log.borrow_mut();
+
+        let end = self.playhead(timeline, offset);
+        let start = *timeline.range().start();
+        let key = (self.revision, filter.as_str().to_owned());
+        let grouping = (!self.show_repeats).then_some(&self.expanded);
+
+        // New updates may join an expanded group, which changes its index
+        let is_stale = log.key.as_ref() != Some(&key)
+            || end < log.end
+            || (end > log.end
+                && grouping.is_some()
+                && log.entries.front().is_some_and(|entry| entry.is_expanded));
+
+        if is_stale {
+            let updates = self.updates(timeline, offset, filter);
+
+            *log = Log {
+                key: Some(key),
+                end,
+                messages: updates.clone().count(),
+                entries: entries(updates, grouping).collect(),
+            };
+
+            return;
+        }
+
+        if end > log.end {
+            let search = self.search.to_lowercase();
+
+            let mut new: Vec<_> = timeline
+                .updates(end)
+                .take_while(|update| update.index > log.end)
+                .filter(|update| filter.matches(&update.message))
+                .filter(|update| {
+                    search.is_empty() || update.message.to_lowercase().contains(&search)
+                })
+                .collect();
+
+            log.end = end;
+            log.messages += new.len();
+
+            while let Some(update) = new.pop() {
+                let head = log.entries.front_mut().filter(|head| {
+                    grouping.is_some()
+                        && timeline
+                            .update(head.index)
+                            .is_some_and(|head| head.variant() == update.variant())
+                });
+
+                match head {
+                    Some(head) => {
+                        head.index = update.index;
+                        head.repeats += 1;
+                    }
+                    None => log.entries.push_front(Entry {
+                        index: update.index,
+                        repeats: 1,
+                        is_expanded: false,
+                    }),
+                }
+            }
+        }
+
+        while log.entries.back().is_some_and(|entry| entry.index < start) {
+            if let Some(entry) = log.entries.pop_back() {
+                log.messages = log.messages.saturating_sub(entry.repeats);
+            }
+        }
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        timeline: &'a Timeline,
+        offset: timeline::Playhead,
+        selection: timeline::Playhead,
+        settings: chart::Settings,
+        filter: &'a filter::Settings,
+    ) -> Element<'a, Message> {
+        self.sync(timeline, offset, &filter.messages);
+
+        let cache = self.log.borrow();
+        let budget = settings.budget.unwrap_or(chart::Budget::FPS_60).duration();
+
+        let messages = cache.messages;
+        let total = cache.entries.len();
+
+        let first = (self.scroll / Self::ROW_HEIGHT).floor() as usize;
+        let visible = (self.height / Self::ROW_HEIGHT).ceil() as usize + 1;
+
+        let rows = cache
+            .entries
+            .iter()
+            .skip(first)
+            .take(visible)
+            .filter_map(|entry| {
+                let update = timeline.update(entry.index)?;
+                let datetime: chrono::DateTime<chrono::Local> = update.at.into();
+                let is_selected = selection == timeline::Playhead::Paused(update.index);
+
+                let expand: Element<'_, Message> = if entry.repeats > 1 {
+                    button(text(if entry.is_expanded { "-" } else { "+" }).size(10))
+                        .width(20)
+                        .padding(0)
+                        .on_press(Message::Expand(update.index))
+                        .style(button::secondary)
+                        .into()
+                } else {
+                    space().width(20).into()
+                };
+
+                let message: Cow<'_, str> = if entry.repeats > 1 && !entry.is_expanded {
+                    format!("{} ×{}", update.variant(), entry.repeats).into()
+                } else {
+                    (&*update.message).into()
+                };
+
+                row![
+                    expand,
+                    button(
+                        row![
+                            text!("#{}", update.number).size(10).width(FillPortion(1)),
+                            text(datetime.format("%H:%M:%S%.3f").to_string())
+                                .size(10)
+                                .width(FillPortion(2)),
+                            text!("{:?}", update.duration)
+                                .size(10)
+                                .width(FillPortion(2))
+                                .style(move |theme: &Theme| slowness(
+                                    theme,
+                                    update.duration,
+                                    budget
+                                )),
+                            text(message)
+                                .size(10)
+                                .wrapping(text::Wrapping::None)
+                                .width(FillPortion(12)),
+                        ]
+                        .spacing(10)
+                        .height(Fill)
+                        .align_y(Center),
+                    )
+                    .width(Fill)
+                    .height(Fill)
+                    .padding([0, 5])
+                    .clip(true)
+                    .on_press(Message::Pin(update.index))
+                    .style(if is_selected {
+                        button::secondary
+                    } else {
+                        button::text
+                    }),
+                ]
+                .spacing(5)
+                .height(Self::ROW_HEIGHT)
+                .align_y(Center)
+                .into()
+            });
+
+        let shown = total.saturating_sub(first).min(visible);
+        let after = total.saturating_sub(first + shown);
+
+        let log = scrollable(
+            column![
+                space().height(first as f32 * Self::ROW_HEIGHT),
+                column(rows),
+                space().height(after as f32 * Self::ROW_HEIGHT),
+            ]
+            .padding([0, 10]),
+        )
+        .on_scroll(Message::Scrolled)
+        .width(Fill)
+        .height(Fill);
+
+        let controls = row![
+            text_input("Search messages...", &self.search)
+                .on_input(Message::SearchChanged)
+                .size(12)
+                .padding(5),
+            text_input("Filter (e.g. !Tick)", filter.messages.as_str())
+                .on_input(Message::FilterChanged)
+                .size(12)
+                .padding(5),
+            toggler(filter.message_rate)
+                .label("Filter message rate")
+                .on_toggle(Message::ToggleFilterRate)
+                .text_size(12)
+                .size(16),
+            button(text("Repeats").size(12))
+                .on_press(Message::ToggleRepeats)
+                .style(if self.show_repeats {
+                    button::primary
+                } else {
+                    button::secondary
+                }),
+            button(text("Follow").size(12))
+                .on_press(Message::ToggleFollow)
+                .style(if self.is_following() {
+                    button::primary
+                } else {
+                    button::secondary
+                }),
+            button(text("Copy").size(12))
+                .on_press(Message::Copy)
+                .style(button::secondary),
+            button(text("Export").size(12))
+                .on_press(Message::Export)
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(Center);
+
+        let breakpoints = row![
+            text_input("Break on (e.g. SaveFile)", &self.breakpoint)
+                .on_input(Message::BreakpointChanged)
+                .on_submit(Message::AddBreakpoint)
+                .size(12)
+                .padding(5)
+                .width(200),
+            toggler(filter.rewind)
+                .label("Rewind on break")
+                .on_toggle(Message::ToggleRewind)
+                .text_size(12)
+                .size(16),
+        ]
+        .extend(
+            filter
+                .breakpoints
+                .iter()
+                .enumerate()
+                .map(|(index, breakpoint)| {
+                    button(text!("{} ×", breakpoint.as_str()).size(12))
+                        .on_press(Message::RemoveBreakpoint(index))
+                        .style(button::danger)
+                        .into()
+                }),
+        )
+        .spacing(10)
+        .align_y(Center);
+
+        let mut updates = timeline.updates(selection);
+        let current = updates.next();
+
+        // When paused, the selected message is compared with the previous one
+        let previous = updates.next().filter(|_| !selection.is_live());
+
+        let changes = current
+            .as_ref()
+            .zip(previous.as_ref())
+            .map(|(current, previous)| {
+                payload::diff(
+                    &payload::parse(&previous.message),
+                    &payload::parse(&current.message),
+                )
+            })
+            .unwrap_or_default();
+
+        let title = match &previous {
+            Some(previous) => format!(
+                "Payload ({} changed since #{})",
+                changes.len(),
+                previous.number
+            ),
+            None => String::from("Payload"),
+        };
+
+        let payload = match current {
+            Some(update) => container(
+                scrollable(payload::view(
+                    &payload::parse(&update.message),
+                    &self.collapsed,
+                    &changes,
+                    Message::TogglePayload,
+                ))
+                .width(Fill)
+                .height(Fill),
+            )
+            .padding([0, 10]),
+            None => container(text("No message has been selected.").size(12)).padding([0, 10]),
+        };
+
+        row![
+            container(card(
+                format!("Messages ({messages})"),
+                column![
+                    container(controls).padding([0, 10]),
+                    container(breakpoints).padding([0, 10]),
+                    log
+                ]
+                .spacing(10),
+            ))
+            .width(FillPortion(3)),
+            container(card(title, payload)).width(FillPortion(2)),
+        ]
+        .spacing(10)
+        .into()
+    }
+}
+
+/// Highlights updates taking over half of the frame budget as warnings,
+/// and those over the whole budget as dangers.
+fn slowness(theme: &Theme, duration: Duration, budget: Duration) -> text::Style {
+    let palette = theme.palette();
+
+    text::Style {
+        color: if duration > budget {
+            Some(palette.danger.base.color)
+        } else if duration > budget / 2 {
+            Some(palette.warning.base.color)
+        } else {
+            None
+        },
+    }
+}
+
+/// The entries of the log as of its last view, newest first.
+#[derive(Debug, Default)]
+struct Log {
+    key: Option<(usize, String)>,
+    end: timeline::Index,
+    messages: usize,
+    entries: VecDeque<Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    index: timeline::Index,
+    repeats: usize,
+    is_expanded: bool,
+}
+
+/// Groups consecutive updates of the same message variant, unless their
+/// group is `expanded`; in which case its updates follow the first one.
+///
+/// Nothing is grouped if `expanded` is `None`.
+fn entries<'a>(
+    updates: impl Iterator<Item = &'a timeline::Update> + 'a,
+    expanded: Option<&'a HashSet<timeline::Index>>,
+) -> impl Iterator<Item = Entry> + 'a {
+    let mut updates = updates.peekable();
+    let mut members = VecDeque::new();
+
+    iter::from_fn(move || {
+        if let Some(index) = members.pop_front() {
+            return Some(Entry {
+                index,
+                repeats: 1,
+                is_expanded: false,
+            });
+        }
+
+        let update = updates.next()?;
+
+        let Some(expanded) = expanded else {
+            return Some(Entry {
+                index: update.index,
+                repeats: 1,
+                is_expanded: false,
+            });
+        };
+
+        let is_expanded = expanded.contains(&update.index);
+        let mut repeats = 1;
+
+        while let Some(next) = updates.next_if(|next| next.variant() == update.variant()) {
+            repeats += 1;
+
+            if is_expanded {
+                members.push_back(next.index);
+            }
+        }
+
+        Some(Entry {
+            index: update.index,
+            repeats,
+            is_expanded,
+        })
+    })
+}
@@ -0,0 +1,358 @@
+use iced::widget::{button, column, row, space, text};
+use iced::{Element, Font, Theme};
+
+use std::collections::{HashMap, HashSet};
+
+#[cfg(test)]
+mod tests;
+
+/// A value parsed from the `Debug` representation of a message.
+///
+/// Parsing is lenient: anything that is not a struct, tuple, list or map
+/// ends up as a leaf with its text untouched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub label: String,
+    pub group: Option<Group>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub open: char,
+    pub close: char,
+    pub children: Vec<Node>,
+}
+
+/// A path to a [`Node`], as the indices of its ancestors' children.
+pub type Path = Vec<usize>;
+
+pub fn parse(debug: &str) -> Node {
+    let mut parser = Parser {
+        chars: debug.chars().collect(),
+        position: 0,
+    };
+
+    parser.node()
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn node(&mut self) -> Node {
+        let key = self.key();
+        let atom = self.atom();
+
+        let label = match key {
+            Some(key) if atom.is_empty() => format!("{key}:"),
+            Some(key) => format!("{key}: {atom}"),
+            None => atom,
+        };
+
+        let close = match self.peek() {
+            Some('{') => '}',
+            Some('(') => ')',
+            Some('[') => ']',
+            _ => return Node { label, group: None },
+        };
+
+        let open = self.next().unwrap_or_default();
+        let mut children = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                None => break,
+                Some(c) if c == close => {
+                    self.position += 1;
+                    break;
+                }
+                Some(',') => {
+                    self.position += 1;
+                }
+                Some('}' | ')' | ']') => {
+                    // Mismatched delimiter; bail out of this group
+                    self.position += 1;
+                    break;
+                }
+                Some(_) => {
+                    let start = self.position;
+                    let child = self.node();
+
+                    if self.position == start {
+                        self.position += 1;
+                    }
+
+                    if !child.label.is_empty() || child.group.is_some() {
+                        children.push(child);
+                    }
+                }
+            }
+        }
+
+        Node {
+            label,
+            group: Some(Group {
+                open,
+                close,
+                children,
+            }),
+        }
+    }
+
+    /// Consumes a `field:` prefix, if any.
+    fn key(&mut self) -> Option<String> {
+        self.skip_whitespace();
+
+        let start = self.position;
+
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.position += 1;
+        }
+
+        let end = self.position;
+        self.skip_whitespace();
+
+        let is_key = end > start
+            && self.peek() == Some(':')
+            && self.chars.get(self.position + 1) != Some(&':');
+
+        if is_key {
+            self.position += 1;
+
+            Some(self.chars[start..end].iter().collect())
+        } else {
+            self.position = start;
+
+            None
+        }
+    }
+
+    /// Consumes everything up to the next delimiter, skipping over string
+    /// and character literals.
+    fn atom(&mut self) -> String {
+        self.skip_whitespace();
+
+        let mut atom = String::new();
+
+        while let Some(c) = self.peek() {
+            match c {
+                '{' | '}' | '(' | ')' | '[' | ']' | ',' => break,
+                '"' => self.literal('"', &mut atom),
+                '\'' if self.is_char_literal() => self.literal('\'', &mut atom),
+                _ => {
+                    atom.push(c);
+                    self.position += 1;
+                }
+            }
+        }
+
+        atom.trim_end().to_owned()
+    }
+
+    fn literal(&mut self, quote: char, output: &mut String) {
+        output.push(quote);
+        self.position += 1;
+
+        let mut is_escaped = false;
+
+        while let Some(c) = self.next() {
+            output.push(c);
+
+            if is_escaped {
+                is_escaped = false;
+            } else if c == '\\' {
+                is_escaped = true;
+            } else if c == quote {
+                break;
+            }
+        }
+    }
+
+    /// Lifetimes (e.g. `'static`) are not closed, while character
+    /// literals always are within a few characters.
+    fn is_char_literal(&self) -> bool {
+        match self.chars.get(self.position + 1..) {
+            Some(['\\', ..]) => true,
+            Some([_, '\'', ..]) => true,
+            _ => false,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+
+        Some(c)
+    }
+}
+
+/// The nodes that changed between two trees, along with their previous
+/// contents.
+pub type Changes = HashMap<Path, String>;
+
+/// Compares two trees node by node, returning the paths of `current` that
+/// differ from `previous`.
+///
+/// A group whose children were added or removed is considered changed as
+/// a whole.
+pub fn diff(previous: &Node, current: &Node) -> Changes {
+    fn compare(previous: &Node, current: &Node, path: &mut Path, changes: &mut Changes) {
+        match (&previous.group, &current.group) {
+            (None, None) if previous.label == current.label => {}
+            (Some(before), Some(after))
+                if previous.label == current.label
+                    && before.open == after.open
+                    && before.children.len() == after.children.len() =>
+            {
+                for (i, (before, after)) in before.children.iter().zip(&after.children).enumerate()
+                {
+                    path.push(i);
+                    compare(before, after, path, changes);
+                    let _ = path.pop();
+                }
+            }
+            _ => {
+                let _ = changes.insert(path.clone(), summary(previous));
+            }
+        }
+    }
+
+    let mut changes = Changes::new();
+
+    compare(previous, current, &mut Vec::new(), &mut changes);
+
+    changes
+}
+
+fn summary(node: &Node) -> String {
+    match &node.group {
+        None => node.label.clone(),
+        Some(group) => format!(
+            "{} {}…{} ({})",
+            node.label,
+            group.open,
+            group.close,
+            group.children.len()
+        )
+        .trim_start()
+        .to_owned(),
+    }
+}
+
+/// Lays out the [`Node`] as an indented tree, where groups can be
+/// collapsed by clicking on them and [`Changes`] are highlighted.
+pub fn view<'a, Message: Clone + 'a>(
+    node: &Node,
+    collapsed: &HashSet<Path>,
+    changes: &Changes,
+    on_toggle: impl Fn(Path) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let mut lines = Vec::new();
+
+    layout(
+        node,
+        &mut Vec::new(),
+        0,
+        collapsed,
+        changes,
+        on_toggle,
+        &mut lines,
+    );
+
+    column(lines).into()
+}
+
+fn layout<'a, Message: Clone + 'a>(
+    node: &Node,
+    path: &mut Path,
+    depth: u16,
+    collapsed: &HashSet<Path>,
+    changes: &Changes,
+    on_toggle: impl Fn(Path) -> Message + Copy + 'a,
+    lines: &mut Vec<Element<'a, Message>>,
+) {
+    let indent = || space().width(depth * 15);
+
+    let change = changes
+        .get(path)
+        .map(|previous| format!("  (was {previous})"));
+    let is_changed = change.is_some();
+
+    let line = move |content: String| {
+        text(content)
+            .size(12)
+            .font(Font::MONOSPACE)
+            .style(move |theme: &Theme| text::Style {
+                color: is_changed.then_some(theme.palette().warning.base.color),
+            })
+    };
+
+    let Some(group) = &node.group else {
+        let content = format!("{}{}", node.label, change.unwrap_or_default());
+
+        lines.push(row![indent(), line(content)].into());
+        return;
+    };
+
+    let label = if node.label.is_empty() {
+        String::new()
+    } else {
+        format!("{} ", node.label)
+    };
+
+    let change = change.unwrap_or_default();
+
+    let toggle = |content: String| {
+        button(line(content))
+            .padding(0)
+            .on_press(on_toggle(path.clone()))
+            .style(button::text)
+    };
+
+    if group.children.is_empty() {
+        lines.push(
+            row![
+                indent(),
+                line(format!("{label}{}{}{change}", group.open, group.close))
+            ]
+            .into(),
+        );
+    } else if collapsed.contains(path) {
+        lines.push(
+            row![
+                indent(),
+                toggle(format!(
+                    "{label}{}…{} ({}){change}",
+                    group.open,
+                    group.close,
+                    group.children.len()
+                ))
+            ]
+            .into(),
+        );
+    } else {
+        lines.push(row![indent(), toggle(format!("{label}{}{change}", group.open))].into());
+
+        for (i, child) in group.children.iter().enumerate() {
+            path.push(i);
+            layout(child, path, depth + 1, collapsed, changes, on_toggle, lines);
+            let _ = path.pop();
+        }
+
+        lines.push(row![indent(), line(group.close.to_string())].into());
+    }
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+fn leaf(label: &str) -> Node {
+    Node {
+        label: label.to_owned(),
+        group: None,
+    }
+}
+
+#[test]
+fn parse_nested_payloads() {
+    let node = parse(r#"Edit(Insert { at: Point { x: 1, y: 2 }, text: "a, (b)" }, [1, 2])"#);
+
+    assert_eq!(node.label, "Edit");
+
+    let group = node.group.expect("tuple variant");
+    assert_eq!((group.open, group.close), ('(', ')'));
+    assert_eq!(group.children.len(), 2);
+
+    let insert = &group.children[0];
+    assert_eq!(insert.label, "Insert");
+
+    let fields = &insert.group.as_ref().expect("struct variant").children;
+    assert_eq!(fields[0].label, "at: Point");
+    assert_eq!(fields[1], leaf(r#"text: "a, (b)""#));
+
+    let list = &group.children[1];
+    assert_eq!(list.label, "");
+    assert_eq!(
+        list.group.as_ref().expect("list").children,
+        vec![leaf("1"), leaf("2")]
+    );
+}
+
+#[test]
+fn parse_unit_and_paths() {
+    assert_eq!(parse("Tick"), leaf("Tick"));
+    assert_eq!(
+        parse("Theme(theme::Dark)"),
+        Node {
+            label: String::from("Theme"),
+            group: Some(Group {
+                open: '(',
+                close: ')',
+                children: vec![leaf("theme::Dark")],
+            }),
+        }
+    );
+}
+
+#[test]
+fn diff_changed_fields() {
+    let previous = parse("Moved { cursor: Point { x: 1, y: 2 }, buttons: [Left] }");
+    let current = parse("Moved { cursor: Point { x: 1, y: 3 }, buttons: [Left, Right] }");
+
+    let changes = diff(&previous, &current);
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes.get(&vec![0, 1]).map(String::as_str), Some("y: 2"));
+    assert_eq!(
+        changes.get(&vec![1]).map(String::as_str),
+        Some("buttons: […] (1)")
+    );
+    assert!(diff(&current, &current).is_empty());
+}
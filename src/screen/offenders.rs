@@ -0,0 +1,138 @@
+use crate::beacon::{Event, Span};
+use crate::stats;
+use crate::timeline::{self, Timeline};
+use crate::widget::card;
+
+use iced::time::Duration;
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Element, Fill, FillPortion};
+
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct Offenders {
+    sort: Sort,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sort {
+    #[default]
+    Total,
+    Max,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SortBy(Sort),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Offender {
+    count: usize,
+    total: Duration,
+    max: Duration,
+}
+
+impl Offenders {
+    /// How far back from the playhead spans are ranked.
+    const WINDOW: Duration = Duration::from_secs(10);
+
+    const MAX_OFFENDERS: usize = 50;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::SortBy(sort) => {
+                self.sort = sort;
+            }
+        }
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        timeline: &'a Timeline,
+        offset: timeline::Playhead,
+    ) -> Element<'a, Message> {
+        let mut offenders: HashMap<(&str, &str), Offender> = HashMap::new();
+
+        for (index, event) in timeline.recent(offset, Self::WINDOW) {
+            let Event::SpanFinished { duration, span, .. } = event else {
+                continue;
+            };
+
+            let details = match span {
+                Span::Update { .. } => timeline.message(index).unwrap_or_default(),
+                _ => "",
+            };
+
+            let offender = offenders.entry((stats::stage(span), details)).or_default();
+
+            offender.count += 1;
+            offender.total += *duration;
+            offender.max = offender.max.max(*duration);
+        }
+
+        let mut offenders: Vec<_> = offenders.into_iter().collect();
+
+        offenders.sort_by_key(|(_, offender)| {
+            std::cmp::Reverse(match self.sort {
+                Sort::Total => offender.total,
+                Sort::Max => offender.max,
+            })
+        });
+
+        let header = {
+            let sort_by = |label, sort| {
+                button(text(label).size(10))
+                    .padding(0)
+                    .on_press(Message::SortBy(sort))
+                    .style(if self.sort == sort {
+                        button::primary
+                    } else {
+                        button::text
+                    })
+                    .width(FillPortion(1))
+            };
+
+            row![
+                text("Stage").size(10).width(FillPortion(1)),
+                text("Message").size(10).width(FillPortion(4)),
+                text("Count").size(10).width(FillPortion(1)),
+                sort_by("Total", Sort::Total),
+                sort_by("Max", Sort::Max),
+            ]
+            .spacing(10)
+        };
+
+        let rows =
+            offenders
+                .into_iter()
+                .take(Self::MAX_OFFENDERS)
+                .map(|((stage, details), offender)| {
+                    row![
+                        text(stage).size(10).width(FillPortion(1)),
+                        text(details).size(10).width(FillPortion(4)),
+                        text!("{}", offender.count).size(10).width(FillPortion(1)),
+                        text!("{:?}", offender.total).size(10).width(FillPortion(1)),
+                        text!("{:?}", offender.max).size(10).width(FillPortion(1)),
+                    ]
+                    .spacing(10)
+                    .into()
+                });
+
+        card(
+            format!("Top Offenders (last {}s)", Self::WINDOW.as_secs()),
+            container(
+                column![
+                    header,
+                    scrollable(column(rows).spacing(5)).width(Fill).height(Fill)
+                ]
+                .spacing(10),
+            )
+            .padding(10),
+        )
+    }
+}
@@ -3,8 +3,8 @@ use crate::chart;
 use crate::timeline::{self, Timeline};
 use crate::widget::card;
 
-use iced::Element;
-use iced::widget::{column, row};
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Element, Fill};
 
 #[derive(Debug, Default)]
 pub struct Overview {
@@ -14,6 +14,8 @@ pub struct Overview {
     interact: chart::Cache,
     draw: chart::Cache,
     present: chart::Cache,
+    frames: chart::Cache,
+    frame_rate: chart::Cache,
 }
 
 impl Overview {
@@ -28,28 +30,32 @@ impl Overview {
         self.interact.clear();
         self.draw.clear();
         self.present.clear();
+        self.frames.clear();
+        self.frame_rate.clear();
     }
 
     pub fn invalidate_by(&mut self, event: &Event) {
         match event {
             Event::SpanFinished { span, .. } => match span {
                 Span::Update { .. } => {
-                    self.update.clear();
+                    self.update.extend();
                 }
                 Span::View { .. } => {
-                    self.view.clear();
+                    self.view.extend();
                 }
                 Span::Layout { .. } => {
-                    self.layout.clear();
+                    self.layout.extend();
                 }
                 Span::Interact { .. } => {
-                    self.interact.clear();
+                    self.interact.extend();
                 }
                 Span::Draw { .. } => {
-                    self.draw.clear();
+                    self.draw.extend();
                 }
                 Span::Present { .. } => {
-                    self.present.clear();
+                    self.present.extend();
+                    self.frames.clear();
+                    self.frame_rate.clear();
                 }
                 _ => {}
             },
@@ -65,7 +71,8 @@ impl Overview {
         timeline: &'a Timeline,
         offset: timeline::Playhead,
         selection: timeline::Playhead,
-        zoom: chart::Zoom,
+        settings: chart::Settings,
+        baseline: &'a chart::Baseline,
     ) -> Element<'a, chart::Interaction> {
         let update = (chart::Stage::Update, &self.update);
         let view = (chart::Stage::View, &self.view);
@@ -74,19 +81,108 @@ impl Overview {
         let draw = (chart::Stage::Draw, &self.draw);
         let present = (chart::Stage::Present, &self.present);
 
+        let frames = card(
+            "Frames",
+            chart::frames(&self.frames, timeline, offset, selection, settings.zoom),
+        );
+
+        let frame_rate = card(
+            "FPS",
+            chart::frame_rate(&self.frame_rate, timeline, offset, selection, settings),
+        );
+
+        let jank = container(jank(
+            timeline,
+            offset,
+            selection,
+            settings.budget.unwrap_or(chart::Budget::FPS_60),
+        ))
+        .width(300);
+
         column(
-            [[update, view], [layout, interact], [draw, present]].map(|charts| {
-                row(charts.into_iter().map(|(stage, cache)| {
-                    card(
-                        stage.to_string(),
-                        chart::performance(stage, cache, timeline, offset, selection, zoom),
-                    )
-                }))
-                .spacing(10)
-                .into()
-            }),
+            [[update, view], [layout, interact], [draw, present]]
+                .map(|charts| {
+                    row(charts.into_iter().map(|(stage, cache)| {
+                        chart::card(
+                            stage.clone(),
+                            chart::performance(
+                                stage, cache, timeline, offset, selection, settings, baseline,
+                            ),
+                        )
+                    }))
+                    .spacing(10)
+                    .into()
+                })
+                .into_iter()
+                .chain([row![frames, frame_rate, jank].spacing(10).into()]),
         )
         .spacing(10)
         .into()
     }
 }
+
+/// Lists the slowest frames over the given budget; clicking one pauses the
+/// timeline at it.
+fn jank<'a>(
+    timeline: &'a Timeline,
+    offset: timeline::Playhead,
+    selection: timeline::Playhead,
+    budget: chart::Budget,
+) -> Element<'a, chart::Interaction> {
+    const MAX_FRAMES: usize = 1_000;
+    const WORST_FRAMES: usize = 20;
+
+    const STAGES: [chart::Stage; 6] = [
+        chart::Stage::Update,
+        chart::Stage::View,
+        chart::Stage::Layout,
+        chart::Stage::Interact,
+        chart::Stage::Draw,
+        chart::Stage::Present,
+    ];
+
+    let mut janky: Vec<_> = timeline
+        .frames(offset)
+        .take(MAX_FRAMES)
+        .filter(|frame| frame.total() > budget.duration())
+        .collect();
+
+    janky.sort_by_key(|frame| std::cmp::Reverse(frame.total()));
+
+    let title = format!("Jank ({} over {:?})", janky.len(), budget.duration());
+
+    let frames = janky.into_iter().take(WORST_FRAMES).map(|frame| {
+        let (worst, duration) = STAGES
+            .iter()
+            .zip(frame.stages())
+            .max_by_key(|(_, duration)| *duration)
+            .expect("frame has stages");
+
+        let is_selected = selection == timeline::Playhead::Paused(frame.index);
+
+        button(
+            row![
+                text!("#{}", frame.index).size(10).width(Fill),
+                text!("{:?}", frame.total()).size(10).width(Fill),
+                text!("{worst} {duration:?}").size(10).width(Fill),
+            ]
+            .spacing(5),
+        )
+        .width(Fill)
+        .padding([2, 5])
+        .on_press(chart::Interaction::Pinned(frame.index))
+        .style(if is_selected {
+            button::secondary
+        } else {
+            button::text
+        })
+        .into()
+    });
+
+    card(
+        title,
+        scrollable(column(frames).padding([0, 5]))
+            .width(Fill)
+            .height(Fill),
+    )
+}
@@ -0,0 +1,209 @@
+use crate::beacon::Event;
+use crate::chart;
+use crate::timeline::{self, Timeline};
+use crate::widget::card;
+
+use iced::theme::palette::Pair;
+use iced::widget::{button, column, container, row, scrollable, space, text};
+use iced::{Background, Center, Color, Element, Fill, FillPortion, Theme};
+
+#[derive(Debug, Default)]
+pub struct Palette;
+
+impl Palette {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        timeline: &'a Timeline,
+        offset: timeline::Playhead,
+        selection: timeline::Playhead,
+        theme: &Theme,
+    ) -> Element<'a, chart::Interaction> {
+        let palette = theme.palette();
+
+        let families = [
+            (
+                "Background",
+                [
+                    palette.background.weak,
+                    palette.background.base,
+                    palette.background.strong,
+                ],
+            ),
+            (
+                "Primary",
+                [
+                    palette.primary.weak,
+                    palette.primary.base,
+                    palette.primary.strong,
+                ],
+            ),
+            (
+                "Secondary",
+                [
+                    palette.secondary.weak,
+                    palette.secondary.base,
+                    palette.secondary.strong,
+                ],
+            ),
+            (
+                "Success",
+                [
+                    palette.success.weak,
+                    palette.success.base,
+                    palette.success.strong,
+                ],
+            ),
+            (
+                "Warning",
+                [
+                    palette.warning.weak,
+                    palette.warning.base,
+                    palette.warning.strong,
+                ],
+            ),
+            (
+                "Danger",
+                [
+                    palette.danger.weak,
+                    palette.danger.base,
+                    palette.danger.strong,
+                ],
+            ),
+        ];
+
+        let header = row![
+            text("Family").size(12).width(100),
+            text("Weak").size(12).width(FillPortion(1)),
+            text("Base").size(12).width(FillPortion(1)),
+            text("Strong").size(12).width(FillPortion(1)),
+        ]
+        .spacing(10);
+
+        let families = families.into_iter().map(|(name, pairs)| {
+            row![text(name).size(12).width(100)]
+                .extend(pairs.into_iter().map(swatch))
+                .spacing(10)
+                .align_y(Center)
+                .into()
+        });
+
+        let history = timeline
+            .seek_with_index(offset)
+            .filter_map(|(index, event)| match event {
+                Event::Connected {
+                    at,
+                    theme: Some(seed),
+                    ..
+                }
+                | Event::ThemeChanged { at, seed } => Some((index, *at, *seed)),
+                _ => None,
+            })
+            .map(|(index, at, seed)| {
+                let datetime: chrono::DateTime<chrono::Local> = at.into();
+                let is_selected = selection == timeline::Playhead::Paused(index);
+
+                button(
+                    row![
+                        text!("#{index}").size(10).width(Fill),
+                        text(datetime.format("%H:%M:%S%.3f").to_string())
+                            .size(10)
+                            .width(Fill),
+                        row![
+                            dot(seed.background),
+                            dot(seed.text),
+                            dot(seed.primary),
+                            dot(seed.success),
+                            dot(seed.warning),
+                            dot(seed.danger),
+                        ]
+                        .spacing(2),
+                    ]
+                    .spacing(5)
+                    .align_y(Center),
+                )
+                .width(Fill)
+                .padding([2, 5])
+                .on_press(chart::Interaction::Pinned(index))
+                .style(if is_selected {
+                    button::secondary
+                } else {
+                    button::text
+                })
+                .into()
+            });
+
+        row![
+            container(card(
+                "Palette",
+                container(column![header, column(families).spacing(10)].spacing(10)).padding(10),
+            ))
+            .width(FillPortion(3)),
+            container(card(
+                "Theme Changes",
+                scrollable(column(history).padding([0, 5]))
+                    .width(Fill)
+                    .height(Fill),
+            ))
+            .width(FillPortion(1)),
+        ]
+        .spacing(10)
+        .into()
+    }
+}
+
+fn swatch<'a, Message: 'a>(pair: Pair) -> Element<'a, Message> {
+    column![
+        container(text(hex(pair.color)).size(12).color(pair.text))
+            .padding(10)
+            .width(Fill)
+            .style(move |_theme: &Theme| container::Style {
+                background: Some(Background::from(pair.color)),
+                ..container::Style::default()
+            }),
+        text!("{:.2}:1 contrast", contrast(pair.color, pair.text)).size(10),
+    ]
+    .spacing(2)
+    .width(FillPortion(1))
+    .into()
+}
+
+fn dot<'a, Message: 'a>(color: Color) -> Element<'a, Message> {
+    container(space())
+        .width(10)
+        .height(10)
+        .style(move |_theme: &Theme| container::Style {
+            background: Some(Background::from(color)),
+            ..container::Style::default()
+        })
+        .into()
+}
+
+fn hex(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Computes the WCAG contrast ratio between two colors.
+fn contrast(a: Color, b: Color) -> f32 {
+    fn luminance(color: Color) -> f32 {
+        let channel = |c: f32| {
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+    }
+
+    let (a, b) = (luminance(a), luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+
+    (lighter + 0.05) / (darker + 0.05)
+}
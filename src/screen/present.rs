@@ -11,6 +11,7 @@ use iced::widget::{column, row};
 pub struct Present {
     present: chart::Cache,
     layers: chart::Cache,
+    frame_rate: chart::Cache,
     quad: Cache,
     triangle: Option<Cache>,
     shader: Option<Cache>,
@@ -26,6 +27,7 @@ impl Present {
     pub fn invalidate(&mut self) {
         self.present.clear();
         self.layers.clear();
+        self.frame_rate.clear();
         self.quad.clear();
         self.text.clear();
 
@@ -48,8 +50,9 @@ impl Present {
                 span: Span::Present { prepare, .. },
                 ..
             } => {
-                self.present.clear();
-                self.layers.clear();
+                self.present.extend();
+                self.layers.extend();
+                self.frame_rate.clear();
 
                 if self.triangle.is_none() && !prepare.triangles.is_zero() {
                     self.triangle = Some(Cache::default());
@@ -63,11 +66,11 @@ impl Present {
                     self.image = Some(Cache::default());
                 }
 
-                self.quad.clear();
-                self.triangle.as_ref().map(Cache::clear);
-                self.shader.as_ref().map(Cache::clear);
-                self.image.as_ref().map(Cache::clear);
-                self.text.clear();
+                self.quad.extend();
+                self.triangle.as_ref().map(Cache::extend);
+                self.shader.as_ref().map(Cache::extend);
+                self.image.as_ref().map(Cache::extend);
+                self.text.extend();
             }
             Event::ThemeChanged { .. } => {
                 self.invalidate();
@@ -81,7 +84,8 @@ impl Present {
         timeline: &'a Timeline,
         offset: timeline::Playhead,
         selection: timeline::Playhead,
-        zoom: chart::Zoom,
+        settings: chart::Settings,
+        baseline: &'a chart::Baseline,
     ) -> Element<'a, chart::Interaction> {
         let primitives = [
             Some((present::Primitive::Quad, &self.quad)),
@@ -103,26 +107,28 @@ impl Present {
             let render_stage = chart::Stage::Render(primitive);
 
             row![
-                card(
-                    prepare_stage.to_string(),
+                chart::card(
+                    prepare_stage.clone(),
                     chart::performance(
                         prepare_stage,
                         &cache.prepare,
                         timeline,
                         offset,
                         selection,
-                        zoom
+                        settings,
+                        baseline
                     )
                 ),
-                card(
-                    render_stage.to_string(),
+                chart::card(
+                    render_stage.clone(),
                     chart::performance(
                         render_stage,
                         &cache.render,
                         timeline,
                         offset,
                         selection,
-                        zoom
+                        settings,
+                        baseline
                     )
                 ),
             ]
@@ -131,20 +137,25 @@ impl Present {
         });
 
         let charts = [row![
-            card(
-                "Present",
+            chart::card(
+                chart::Stage::Present,
                 chart::performance(
                     chart::Stage::Present,
                     &self.present,
                     timeline,
                     offset,
                     selection,
-                    zoom,
+                    settings,
+                    baseline
                 ),
             ),
             card(
                 "Layers",
-                chart::layers_rendered(&self.layers, timeline, offset, selection, zoom),
+                chart::layers_rendered(&self.layers, timeline, offset, selection, settings),
+            ),
+            card(
+                "FPS",
+                chart::frame_rate(&self.frame_rate, timeline, offset, selection, settings),
             ),
         ]
         .spacing(10)
@@ -167,4 +178,9 @@ impl Cache {
         self.prepare.clear();
         self.render.clear();
     }
+
+    fn extend(&self) {
+        self.prepare.extend();
+        self.render.extend();
+    }
 }
@@ -0,0 +1,73 @@
+use crate::stats;
+use crate::timeline::{self, Timeline};
+use crate::widget::card;
+
+use iced::time::Duration;
+use iced::widget::{column, container, row, scrollable, text};
+use iced::{Element, Fill, FillPortion};
+
+#[derive(Debug, Default)]
+pub struct Stats;
+
+impl Stats {
+    /// How far back from the playhead spans are measured.
+    const WINDOW: Duration = Duration::from_secs(10);
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn view<'a, Message: 'a>(
+        &'a self,
+        timeline: &'a Timeline,
+        offset: timeline::Playhead,
+    ) -> Element<'a, Message> {
+        let mut collector = stats::Collector::new();
+
+        for (_, event) in timeline.recent(offset, Self::WINDOW) {
+            collector.push(event);
+        }
+
+        let cell = |content: String, portion| text(content).size(12).width(FillPortion(portion));
+
+        let header = row![
+            cell(String::from("Stage"), 3),
+            cell(String::from("Count"), 1),
+            cell(String::from("Mean"), 1),
+            cell(String::from("p50"), 1),
+            cell(String::from("p95"), 1),
+            cell(String::from("p99"), 1),
+            cell(String::from("Max"), 1),
+        ]
+        .spacing(10);
+
+        let rows = collector
+            .statistics()
+            .into_iter()
+            .map(|(stage, statistics)| {
+                row![
+                    cell(stage, 3),
+                    cell(statistics.count.to_string(), 1),
+                    cell(format!("{:?}", statistics.mean), 1),
+                    cell(format!("{:?}", statistics.p50), 1),
+                    cell(format!("{:?}", statistics.p95), 1),
+                    cell(format!("{:?}", statistics.p99), 1),
+                    cell(format!("{:?}", statistics.max), 1),
+                ]
+                .spacing(10)
+                .into()
+            });
+
+        card(
+            format!("Statistics (last {}s)", Self::WINDOW.as_secs()),
+            container(
+                column![
+                    header,
+                    scrollable(column(rows).spacing(5)).width(Fill).height(Fill)
+                ]
+                .spacing(10),
+            )
+            .padding(10),
+        )
+    }
+}
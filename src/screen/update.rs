@@ -1,18 +1,50 @@
 use crate::beacon::{Event, Span};
 use crate::chart;
+use crate::filter::Filter;
 use crate::timeline::{self, Timeline};
 use crate::widget::card;
 
 use iced::padding;
-use iced::widget::{column, container, row, scrollable, text};
+use iced::time::Duration;
+use iced::widget::{button, column, container, row, scrollable, text};
 use iced::{Element, Fill, FillPortion};
 
+use std::collections::HashMap;
+
 #[derive(Debug, Default)]
 pub struct Update {
     update: chart::Cache,
     tasks_spawned: chart::Cache,
     subscriptions_alive: chart::Cache,
     message_rate: chart::Cache,
+    sort: Sort,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Chart(chart::Interaction),
+    SortBy(Sort),
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    ChartInteracted(chart::Interaction),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sort {
+    Count,
+    #[default]
+    Total,
+    Mean,
+    Max,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Variant {
+    count: u32,
+    total: Duration,
+    max: Duration,
 }
 
 impl Update {
@@ -33,10 +65,10 @@ impl Update {
                 span: Span::Update { .. },
                 ..
             } => {
-                self.update.clear();
-                self.tasks_spawned.clear();
+                self.update.extend();
+                self.tasks_spawned.extend();
                 self.message_rate.clear();
-                self.subscriptions_alive.clear();
+                self.subscriptions_alive.extend();
             }
             Event::ThemeChanged { .. } => {
                 self.invalidate();
@@ -45,32 +77,59 @@ impl Update {
         }
     }
 
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::Chart(interaction) => Some(Event::ChartInteracted(interaction)),
+            Message::SortBy(sort) => {
+                self.sort = sort;
+
+                None
+            }
+        }
+    }
+
     pub fn view<'a>(
         &'a self,
         timeline: &'a Timeline,
         offset: timeline::Playhead,
         selection: timeline::Playhead,
-        zoom: chart::Zoom,
-    ) -> Element<'a, chart::Interaction> {
-        let update = chart::updates(&self.update, timeline, offset, selection, zoom);
+        settings: chart::Settings,
+        baseline: &'a chart::Baseline,
+        filter: Option<&Filter>,
+    ) -> Element<'a, Message> {
+        let update = chart::performance(
+            chart::Stage::Update,
+            &self.update,
+            timeline,
+            offset,
+            selection,
+            settings,
+            baseline,
+        );
         let tasks_spawned =
-            chart::tasks_spawned(&self.tasks_spawned, timeline, offset, selection, zoom);
+            chart::tasks_spawned(&self.tasks_spawned, timeline, offset, selection, settings);
         let subscriptions_alive = chart::subscriptions_alive(
             &self.subscriptions_alive,
             timeline,
             offset,
             selection,
-            zoom,
+            settings,
+        );
+        let message_rate = chart::message_rate(
+            &self.message_rate,
+            timeline,
+            offset,
+            selection,
+            settings,
+            filter,
         );
-        let message_rate =
-            chart::message_rate(&self.message_rate, timeline, offset, selection, zoom);
 
         let last_message = container(
             scrollable({
                 let message = timeline
                     .updates(selection)
                     .next()
-                    .map(|update| update.message)
+                    .map(|update| &*update.message)
                     .unwrap_or_default();
 
                 text(message).size(10)
@@ -83,19 +142,105 @@ impl Update {
 
         row![
             column![
-                container(card("Update", update)).height(FillPortion(2)),
-                card("Tasks Spawned", tasks_spawned),
-                card("Subscriptions Alive", subscriptions_alive),
+                container(chart::card(chart::Stage::Update, update).map(Message::Chart))
+                    .height(FillPortion(2)),
+                card("Tasks Spawned", tasks_spawned.map(Message::Chart)),
+                card(
+                    "Subscriptions Alive",
+                    subscriptions_alive.map(Message::Chart)
+                ),
             ]
             .width(FillPortion(2))
             .spacing(10),
             column![
                 container(card("Last Message", last_message)).height(FillPortion(2)),
-                card("Message Rate", message_rate),
+                container(card("Variants", self.variants(timeline, offset))).height(FillPortion(2)),
+                card(
+                    if filter.is_some_and(|filter| !filter.is_empty()) {
+                        "Message Rate (filtered)"
+                    } else {
+                        "Message Rate"
+                    },
+                    message_rate.map(Message::Chart)
+                ),
             ]
             .spacing(10)
         ]
         .spacing(10)
         .into()
     }
+
+    /// Aggregates the update durations before the playhead per message
+    /// variant.
+    fn variants<'a>(
+        &self,
+        timeline: &'a Timeline,
+        offset: timeline::Playhead,
+    ) -> Element<'a, Message> {
+        let mut variants: HashMap<String, Variant> = HashMap::new();
+
+        for update in timeline.updates(offset) {
+            let variant = variants.entry(update.variant().to_owned()).or_default();
+
+            variant.count += 1;
+            variant.total += update.duration;
+            variant.max = variant.max.max(update.duration);
+        }
+
+        let mut variants: Vec<_> = variants.into_iter().collect();
+
+        variants.sort_by(|(_, a), (_, b)| match self.sort {
+            Sort::Count => b.count.cmp(&a.count),
+            Sort::Total => b.total.cmp(&a.total),
+            Sort::Mean => (b.total / b.count).cmp(&(a.total / a.count)),
+            Sort::Max => b.max.cmp(&a.max),
+        });
+
+        let header = {
+            let sort_by = |label, sort| {
+                button(text(label).size(10))
+                    .padding(0)
+                    .on_press(Message::SortBy(sort))
+                    .style(if self.sort == sort {
+                        button::primary
+                    } else {
+                        button::text
+                    })
+                    .width(FillPortion(1))
+            };
+
+            row![
+                text("Variant").size(10).width(FillPortion(3)),
+                sort_by("Count", Sort::Count),
+                sort_by("Total", Sort::Total),
+                sort_by("Mean", Sort::Mean),
+                sort_by("Max", Sort::Max),
+            ]
+            .spacing(10)
+        };
+
+        let rows = variants.into_iter().map(|(name, variant)| {
+            row![
+                text(name).size(10).width(FillPortion(3)),
+                text!("{}", variant.count).size(10).width(FillPortion(1)),
+                text!("{:?}", variant.total).size(10).width(FillPortion(1)),
+                text!("{:?}", variant.total / variant.count)
+                    .size(10)
+                    .width(FillPortion(1)),
+                text!("{:?}", variant.max).size(10).width(FillPortion(1)),
+            ]
+            .spacing(10)
+            .into()
+        });
+
+        container(
+            column![
+                header,
+                scrollable(column(rows).spacing(5)).width(Fill).height(Fill)
+            ]
+            .spacing(10),
+        )
+        .padding(padding::all(10).top(0))
+        .into()
+    }
 }
@@ -0,0 +1,331 @@
+use crate::beacon;
+use crate::beacon::span::Span;
+use crate::timeline;
+
+use iced::futures::{SinkExt, Stream};
+use iced::stream;
+use iced::theme;
+use iced::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+pub const EXTENSION: &str = "comet";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Entry {
+    Connected {
+        at: SystemTime,
+        name: String,
+        version: beacon::Version,
+        theme: Option<theme::Seed>,
+        can_time_travel: bool,
+    },
+    Disconnected {
+        at: SystemTime,
+    },
+    ThemeChanged {
+        at: SystemTime,
+        seed: theme::Seed,
+    },
+    SpanFinished {
+        at: SystemTime,
+        duration: Duration,
+        span: Span,
+    },
+    QuitRequested {
+        at: SystemTime,
+    },
+    AlreadyRunning {
+        at: SystemTime,
+    },
+}
+
+impl From<&beacon::Event> for Entry {
+    fn from(event: &beacon::Event) -> Self {
+        Self::from(event.clone())
+    }
+}
+
+impl From<beacon::Event> for Entry {
+    fn from(event: beacon::Event) -> Self {
+        match event {
+            beacon::Event::Connected {
+                at,
+                name,
+                version,
+                theme,
+                can_time_travel,
+                ..
+            } => Self::Connected {
+                at,
+                name,
+                version,
+                theme,
+                can_time_travel,
+            },
+            beacon::Event::Disconnected { at } => Self::Disconnected { at },
+            beacon::Event::ThemeChanged { at, seed } => Self::ThemeChanged { at, seed },
+            beacon::Event::SpanFinished { at, duration, span } => {
+                Self::SpanFinished { at, duration, span }
+            }
+            beacon::Event::QuitRequested { at } => Self::QuitRequested { at },
+            beacon::Event::AlreadyRunning { at } => Self::AlreadyRunning { at },
+        }
+    }
+}
+
+impl Entry {
+    pub fn at(&self) -> SystemTime {
+        match self {
+            Self::Connected { at, .. }
+            | Self::Disconnected { at }
+            | Self::ThemeChanged { at, .. }
+            | Self::SpanFinished { at, .. }
+            | Self::QuitRequested { at }
+            | Self::AlreadyRunning { at } => *at,
+        }
+    }
+
+    /// Turns the [`Entry`] back into a beacon event.
+    ///
+    /// [`Entry::Connected`] has no live connection to restore, so it yields
+    /// `None`.
+    pub fn into_event(self) -> Option<beacon::Event> {
+        Some(match self {
+            Self::Connected { .. } => return None,
+            Self::Disconnected { at } => beacon::Event::Disconnected { at },
+            Self::ThemeChanged { at, seed } => beacon::Event::ThemeChanged { at, seed },
+            Self::SpanFinished { at, duration, span } => {
+                beacon::Event::SpanFinished { at, duration, span }
+            }
+            Self::QuitRequested { at } => beacon::Event::QuitRequested { at },
+            Self::AlreadyRunning { at } => beacon::Event::AlreadyRunning { at },
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Recording {
+    pub start: timeline::Index,
+    pub started_at: SystemTime,
+}
+
+impl Recording {
+    /// Starts recording over from the given index, as a new session.
+    pub fn restart(&mut self, start: timeline::Index) {
+        self.start = start;
+        self.started_at = SystemTime::now();
+    }
+}
+
+pub fn save(path: &Path, events: impl IntoIterator<Item = beacon::Event>) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    for event in events {
+        serde_json::to_writer(&mut file, &Entry::from(event))?;
+        file.write_all(b"\n")?;
+    }
+
+    file.flush()
+}
+
+pub async fn load(path: PathBuf) -> Result<Vec<Entry>, String> {
+    read(&path).map_err(|error| error.to_string())
+}
+
+pub fn read(path: &Path) -> io::Result<Vec<Entry>> {
+    Reader::open(path).and_then(|mut reader| reader.read())
+}
+
+/// Appends events to a session file.
+///
+/// Writes are buffered; [`Recorder::flush`] should be called once a batch of
+/// events has been recorded.
+#[derive(Debug)]
+pub struct Recorder {
+    file: BufWriter<File>,
+}
+
+impl Recorder {
+    pub fn append(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            file: BufWriter::new(file),
+        })
+    }
+
+    pub fn record(&mut self, event: &beacon::Event) -> io::Result<()> {
+        serde_json::to_writer(&mut self.file, &Entry::from(event))?;
+        self.file.write_all(b"\n")
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Err(error) = self.flush() {
+            log::error!("Failed to flush recording: {error}");
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Reader {
+    file: BufReader<File>,
+    line: String,
+}
+
+impl Reader {
+    fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            file: BufReader::new(File::open(path)?),
+            line: String::new(),
+        })
+    }
+
+    fn read(&mut self) -> io::Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+
+        loop {
+            let read = self.file.read_line(&mut self.line)?;
+
+            // A partial line may still be in the middle of being written;
+            // keep it around until the rest of it shows up.
+            if read == 0 || !self.line.ends_with('\n') {
+                break;
+            }
+
+            entries.push(serde_json::from_str(&self.line)?);
+            self.line.clear();
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Follows the session file at the given path, producing new entries as
+/// they are appended to it.
+pub fn tail(path: &PathBuf) -> impl Stream<Item = Vec<Entry>> + use<> {
+    let path = path.clone();
+
+    stream::channel(10, async move |mut output| {
+        let mut reader = loop {
+            match Reader::open(&path) {
+                Ok(reader) => break reader,
+                Err(error) => {
+                    log::warn!("Waiting for {}: {error}", path.display());
+                    tokio::time::sleep(TAIL_INTERVAL * 4).await;
+                }
+            }
+        };
+
+        loop {
+            match reader.read() {
+                Ok(entries) if !entries.is_empty() => {
+                    let _ = output.send(entries).await;
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    log::error!("Failed to read {}: {error}", path.display());
+                    return;
+                }
+            }
+
+            tokio::time::sleep(TAIL_INTERVAL).await;
+        }
+    })
+}
+
+const TAIL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A session file played back as if its events were happening live.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub path: PathBuf,
+    /// How much faster than the original the session is played back.
+    pub speed: f64,
+}
+
+impl Hash for Replay {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        self.speed.to_bits().hash(state);
+    }
+}
+
+/// Plays back the session file of the given [`Replay`], producing its
+/// entries with the same pacing they were originally recorded with.
+pub fn replay(replay: &Replay) -> impl Stream<Item = Vec<Entry>> + use<> {
+    let Replay { path, speed } = replay.clone();
+
+    stream::channel(10, async move |mut output| {
+        let entries = match load(path.clone()).await {
+            Ok(entries) => entries,
+            Err(error) => {
+                log::error!("Failed to replay {}: {error}", path.display());
+                return;
+            }
+        };
+
+        let mut batch = Vec::new();
+        let mut previous = None;
+        let mut pending = Duration::ZERO;
+
+        for entry in entries {
+            let at = entry.at();
+
+            pending += previous
+                .and_then(|previous| at.duration_since(previous).ok())
+                .unwrap_or_default()
+                .div_f64(speed);
+
+            previous = Some(at);
+
+            // Small gaps are batched together to avoid flooding the runtime
+            if pending >= REPLAY_INTERVAL {
+                if !batch.is_empty() {
+                    let _ = output.send(std::mem::take(&mut batch)).await;
+                }
+
+                tokio::time::sleep(pending).await;
+                pending = Duration::ZERO;
+            }
+
+            batch.push(entry);
+        }
+
+        if !batch.is_empty() {
+            let _ = output.send(batch).await;
+        }
+
+        log::info!("Finished replaying {}", path.display());
+    })
+}
+
+const REPLAY_INTERVAL: Duration = Duration::from_millis(16);
+
+pub fn path(directory: &Path, name: &str, time: SystemTime) -> PathBuf {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+
+    let datetime: chrono::DateTime<chrono::Local> = time.into();
+
+    directory.join(format!(
+        "{name}-{}.{EXTENSION}",
+        datetime.format("%Y%m%d-%H%M%S")
+    ))
+}
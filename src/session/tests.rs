@@ -0,0 +1,46 @@
+use super::*;
+
+use crate::core::window;
+use crate::timeline::Timeline;
+
+fn view(at: SystemTime, window: window::Id) -> beacon::Event {
+    beacon::Event::SpanFinished {
+        at,
+        duration: Duration::from_millis(1),
+        span: Span::View { window },
+    }
+}
+
+#[test]
+fn recording_restarts_with_a_cleared_timeline() {
+    let mut timeline = Timeline::new();
+    let window = window::Id::unique();
+    let now = SystemTime::now();
+
+    for _ in 0..10 {
+        timeline.push(view(now, window));
+    }
+
+    let mut recording = Recording {
+        start: timeline.end(),
+        started_at: now,
+    };
+
+    timeline.clear();
+    recording.restart(timeline.end());
+
+    for _ in 0..3 {
+        timeline.push(view(now, window));
+    }
+
+    let path = std::env::temp_dir().join(format!(
+        "comet-recording-{}.{EXTENSION}",
+        std::process::id()
+    ));
+
+    save(&path, timeline.restore(recording.start)).expect("Save session");
+    let entries = read(&path).expect("Read session");
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(entries.len(), 3);
+}
@@ -0,0 +1,130 @@
+use crate::beacon;
+use crate::beacon::span::Span;
+use crate::export;
+
+use iced::time::Duration;
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Statistics {
+    pub count: usize,
+    pub mean: Duration,
+    pub min: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl Statistics {
+    pub fn new(durations: impl IntoIterator<Item = Duration>) -> Option<Self> {
+        let mut durations: Vec<_> = durations.into_iter().collect();
+
+        if durations.is_empty() {
+            return None;
+        }
+
+        durations.sort_unstable();
+
+        let count = durations.len();
+        let total: Duration = durations.iter().sum();
+
+        Some(Self {
+            count,
+            mean: total / count as u32,
+            min: durations[0],
+            p50: percentile(&durations, 0.50),
+            p95: percentile(&durations, 0.95),
+            p99: percentile(&durations, 0.99),
+            max: durations[count - 1],
+        })
+    }
+}
+
+/// Returns the nearest-rank percentile of some sorted values.
+pub fn percentile<T: Copy>(sorted: &[T], percentile: f64) -> T {
+    let rank = (percentile * sorted.len() as f64).ceil() as usize;
+
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// How many of the preceding values make up the rolling baseline of
+/// [`anomalies`].
+pub const BASELINE: usize = 50;
+
+/// How many standard deviations away from the baseline a value must be to
+/// be considered an anomaly.
+pub const DEVIATIONS: f64 = 3.0;
+
+/// Flags the values that deviate from the rolling baseline of the
+/// [`BASELINE`] values before them by more than [`DEVIATIONS`] standard
+/// deviations.
+///
+/// Values are expected newest first, like the timeline yields them. Values
+/// without a full baseline behind them are never flagged.
+pub fn anomalies(values: &[f64]) -> Vec<bool> {
+    let mut anomalies = vec![false; values.len()];
+    let mut sum = 0.0;
+    let mut squares = 0.0;
+
+    for i in (0..values.len()).rev() {
+        let value = values[i];
+
+        if i + BASELINE < values.len() {
+            let n = BASELINE as f64;
+            let mean = sum / n;
+            let deviation = (squares / n - mean * mean).max(0.0).sqrt();
+
+            anomalies[i] = deviation > 0.0 && (value - mean).abs() > DEVIATIONS * deviation;
+
+            let oldest = values[i + BASELINE];
+
+            sum -= oldest;
+            squares -= oldest * oldest;
+        }
+
+        sum += value;
+        squares += value * value;
+    }
+
+    anomalies
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Collector {
+    stages: BTreeMap<String, Vec<Duration>>,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, event: &beacon::Event) {
+        let beacon::Event::SpanFinished { duration, span, .. } = event else {
+            return;
+        };
+
+        self.stages
+            .entry(stage(span).to_owned())
+            .or_default()
+            .push(*duration);
+    }
+
+    pub fn statistics(&self) -> BTreeMap<String, Statistics> {
+        self.stages
+            .iter()
+            .filter_map(|(stage, durations)| {
+                Some((stage.clone(), Statistics::new(durations.iter().copied())?))
+            })
+            .collect()
+    }
+}
+
+pub fn stage(span: &Span) -> &str {
+    match span {
+        Span::Custom { name } => name,
+        span => export::stage(span),
+    }
+}
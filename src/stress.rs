@@ -0,0 +1,114 @@
+use crate::beacon::{self, client, span};
+use crate::core::window;
+
+use iced::time::{Duration, Instant};
+
+use std::thread;
+
+const MESSAGES: &[&str] = &[
+    "Tick(Instant { tv_sec: 1, tv_nsec: 0 })",
+    "Increment",
+    "TextChanged(\"stress\")",
+    "Scrolled(Viewport { offset: 42.0 })",
+];
+
+/// Generates synthetic beacon traffic against a running comet instance.
+///
+/// Every 10ms tick sends its share of `events_per_second` spans, cycling
+/// through the stages of a frame (update, view, layout, interact, draw and
+/// present). The fractional share of a tick carries over to the next one,
+/// so low rates send a span only every few ticks.
+pub fn run(events_per_second: u32, duration: Option<Duration>) {
+    if !beacon::is_running() {
+        log::warn!("comet is not running. Nothing to stress!");
+        return;
+    }
+
+    let client = client::connect(client::Metadata {
+        name: "comet stress",
+        theme: None,
+        can_time_travel: false,
+    });
+
+    let window = window::Id::unique();
+    let started_at = Instant::now();
+    let interval = Duration::from_millis(10);
+    // Fractions of a span carry over, so rates below one span per tick hold
+    let per_tick = events_per_second as f64 * interval.as_secs_f64();
+
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+    let mut sent = 0u64;
+    let mut budget = 0.0;
+    let mut number = 0;
+    let mut report = started_at;
+    let mut next_tick = started_at;
+
+    while duration.is_none_or(|duration| started_at.elapsed() < duration) {
+        budget += per_tick;
+
+        while budget >= 1.0 {
+            let stage = match sent % 6 {
+                0 => {
+                    number += 1;
+
+                    client.log(client::Event::SpanStarted(span::Stage::Update));
+                    client.log(client::Event::MessageLogged {
+                        number,
+                        message: MESSAGES[random.generate() as usize % MESSAGES.len()].to_owned(),
+                    });
+                    client.log(client::Event::CommandsSpawned(
+                        random.generate() as usize % 3,
+                    ));
+                    client.log(client::Event::SubscriptionsTracked(4));
+
+                    span::Stage::Update
+                }
+                1 => span::Stage::View(window),
+                2 => span::Stage::Layout(window),
+                3 => span::Stage::Interact(window),
+                4 => span::Stage::Draw(window),
+                _ => span::Stage::Present(window),
+            };
+
+            // Mostly fast spans with an occasional slow outlier
+            let micros = if random.generate() % 50 == 0 {
+                5_000 + random.generate() % 20_000
+            } else {
+                100 + random.generate() % 2_000
+            };
+
+            client.log(client::Event::SpanFinished(
+                stage,
+                Duration::from_micros(micros),
+            ));
+
+            sent += 1;
+            budget -= 1.0;
+        }
+
+        if report.elapsed() >= Duration::from_secs(1) {
+            log::info!(
+                "{sent} spans sent ({:.0}/s)",
+                sent as f64 / started_at.elapsed().as_secs_f64()
+            );
+
+            report = Instant::now();
+        }
+
+        next_tick += interval;
+        thread::sleep(next_tick.saturating_duration_since(Instant::now()));
+    }
+}
+
+/// A tiny xorshift generator; good enough to make synthetic traffic look
+/// less regular.
+struct Random(u64);
+
+impl Random {
+    fn generate(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -0,0 +1,167 @@
+use crate::beacon;
+use crate::session;
+use crate::stats::{self, Statistics};
+
+use iced::time::{Duration, SystemTime};
+
+use serde::Serialize;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A machine-readable summary of a headless run, printed as a single JSON
+/// line to stdout on exit.
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub mode: &'static str,
+    pub output: Option<String>,
+    pub app: Option<App>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub events: usize,
+    pub stages: BTreeMap<String, Stage>,
+    /// The budgets evaluated by `check`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub thresholds: Vec<Threshold>,
+    /// The stages of `check` compared against a baseline session.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub baseline: Vec<Comparison>,
+    /// Whether every budget of `check` was met and nothing regressed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct App {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Stage {
+    pub count: usize,
+    pub mean_us: f64,
+    pub min_us: f64,
+    pub p50_us: f64,
+    pub p95_us: f64,
+    pub p99_us: f64,
+    pub max_us: f64,
+}
+
+/// The outcome of a single budget of `check`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Threshold {
+    pub name: String,
+    pub limit_us: f64,
+    /// The value of the statistic, if the stage has any samples.
+    pub observed_us: Option<f64>,
+    pub samples: usize,
+    pub pass: bool,
+}
+
+/// A statistic of a stage compared against a baseline session.
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    pub stage: String,
+    pub statistic: &'static str,
+    pub baseline_us: f64,
+    pub observed_us: f64,
+    pub change_pct: f64,
+    pub pass: bool,
+}
+
+pub fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+impl From<Statistics> for Stage {
+    fn from(statistics: Statistics) -> Self {
+        Self {
+            count: statistics.count,
+            mean_us: micros(statistics.mean),
+            min_us: micros(statistics.min),
+            p50_us: micros(statistics.p50),
+            p95_us: micros(statistics.p95),
+            p99_us: micros(statistics.p99),
+            max_us: micros(statistics.max),
+        }
+    }
+}
+
+/// Keeps track of everything a [`Summary`] needs while events are captured.
+#[derive(Debug, Clone, Default)]
+pub struct Capture {
+    app: Option<App>,
+    started_at: Option<SystemTime>,
+    finished_at: Option<SystemTime>,
+    events: usize,
+    stages: stats::Collector,
+}
+
+impl Capture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, event: &beacon::Event) {
+        if let beacon::Event::Connected { name, version, .. } = event {
+            self.app = Some(App {
+                name: name.clone(),
+                version: version.to_string(),
+            });
+        }
+
+        let at = event.at();
+
+        self.started_at = Some(self.started_at.map_or(at, |start| start.min(at)));
+        self.finished_at = Some(self.finished_at.map_or(at, |end| end.max(at)));
+        self.events += 1;
+        self.stages.push(event);
+    }
+
+    /// Like [`push`](Self::push), but also takes the app metadata that
+    /// only survives as a [`session::Entry::Connected`].
+    pub fn push_entry(&mut self, entry: session::Entry) {
+        if let session::Entry::Connected { name, version, .. } = &entry {
+            self.app = Some(App {
+                name: name.clone(),
+                version: version.to_string(),
+            });
+        }
+
+        if let Some(event) = entry.into_event() {
+            self.push(&event);
+        }
+    }
+
+    pub fn summary(&self, mode: &'static str, output: Option<&Path>) -> Summary {
+        let format = |time: SystemTime| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339();
+
+        Summary {
+            mode,
+            output: output.map(|path| path.display().to_string()),
+            app: self.app.clone(),
+            started_at: self.started_at.map(format),
+            finished_at: self.finished_at.map(format),
+            events: self.events,
+            stages: self
+                .stages
+                .statistics()
+                .into_iter()
+                .map(|(stage, statistics)| (stage, Stage::from(statistics)))
+                .collect(),
+            thresholds: Vec::new(),
+            baseline: Vec::new(),
+            passed: None,
+        }
+    }
+}
+
+impl Summary {
+    pub fn print(&self) {
+        match serde_json::to_string(self) {
+            Ok(json) => println!("{json}"),
+            Err(error) => log::error!("Failed to serialize summary: {error}"),
+        }
+    }
+}
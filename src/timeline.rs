@@ -1,17 +1,32 @@
 use crate::beacon;
 use crate::beacon::span;
 use crate::core::time::{Duration, SystemTime};
+use crate::core::window;
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
+use std::iter;
+use std::mem;
 use std::ops::{Add, RangeInclusive, Sub};
+use std::sync::Arc;
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Debug, Clone, Default)]
 pub struct Timeline {
     events: VecDeque<beacon::Event>,
     updates: VecDeque<Update>,
+    messages: Messages,
     update_rate: VecDeque<Bucket>,
+    frame_rate: VecDeque<Bucket>,
+    runs: VecDeque<Run>,
+    stages: Stages,
+    milestones: VecDeque<Milestone>,
+    windows: Vec<window::Id>,
     removed: usize,
+    retention: Option<Duration>,
+    bytes: usize,
 }
 
 impl Timeline {
@@ -22,6 +37,14 @@ impl Timeline {
         Self::default()
     }
 
+    /// Sets how long events are kept for, relative to the newest one.
+    ///
+    /// Older events are trimmed as new ones are pushed, on top of the
+    /// maximum amount of events.
+    pub fn set_retention(&mut self, retention: Option<Duration>) {
+        self.retention = retention;
+    }
+
     pub fn capacity(&self) -> usize {
         Self::MAX_SIZE
     }
@@ -30,6 +53,11 @@ impl Timeline {
         self.events.len()
     }
 
+    /// Returns an estimate of the memory used by the events, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.bytes + self.messages.bytes
+    }
+
     pub fn range(&self) -> RangeInclusive<Index> {
         Index(self.removed)..=self.end()
     }
@@ -45,14 +73,18 @@ impl Timeline {
         }
     }
 
-    pub fn push(&mut self, event: beacon::Event) {
+    /// Appends an event to the timeline.
+    ///
+    /// The message of an update is moved into its interned [`Update`], so
+    /// the stored event keeps an empty one; see [`Timeline::message`].
+    pub fn push(&mut self, mut event: beacon::Event) {
         if let beacon::Event::SpanFinished {
             span:
                 span::Span::Update {
                     number,
                     tasks,
                     subscriptions,
-                    ref message,
+                    ref mut message,
                     ..
                 },
             at,
@@ -62,60 +94,144 @@ impl Timeline {
         {
             self.updates.push_back(Update {
                 index: self.end() + 1,
-                message: message.clone(),
+                at,
+                message: self.messages.intern(&mem::take(message)),
                 duration,
                 number,
                 tasks,
                 subscriptions,
             });
 
-            let second = at
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
+            count(&mut self.update_rate, self.end() + 1, at);
+        }
 
-            match self.update_rate.back_mut() {
-                Some(update_rate) if update_rate.second == second => {
-                    update_rate.at = at;
-                    update_rate.total += 1;
-                }
-                _ => {
-                    self.update_rate.push_back(Bucket {
-                        index: self.end() + 1,
-                        at,
-                        second,
-                        total: 1,
-                    });
-                }
+        if let beacon::Event::SpanFinished {
+            span: span::Span::Present { .. },
+            at,
+            ..
+        } = event
+        {
+            count(&mut self.frame_rate, self.end() + 1, at);
+        }
+
+        if let beacon::Event::SpanFinished {
+            span:
+                span::Span::View { window }
+                | span::Span::Layout { window }
+                | span::Span::Interact { window }
+                | span::Span::Draw { window }
+                | span::Span::Present { window, .. },
+            ..
+        } = event
+            && !self.windows.contains(&window)
+        {
+            self.windows.push(window);
+        }
+
+        if let beacon::Event::Connected { at, .. } = event {
+            self.runs.push_back(Run {
+                number: self.runs.back().map_or(1, |run| run.number + 1),
+                index: self.end() + 1,
+                at,
+            });
+        }
+
+        if let beacon::Event::SpanFinished { span, .. } = &event {
+            self.stages.push(span, self.end() + 1);
+        }
+
+        match event {
+            beacon::Event::Disconnected { .. } => {
+                self.milestones
+                    .push_back(Milestone::Disconnected(self.end() + 1));
+            }
+            beacon::Event::ThemeChanged { .. } => {
+                self.milestones
+                    .push_back(Milestone::ThemeChanged(self.end() + 1));
             }
+            _ => {}
         }
 
+        let at = event.at();
+
+        self.bytes += footprint(&event);
         self.events.push_back(event);
 
         if self.events.len() > Self::MAX_SIZE {
-            if let Some(beacon::Event::SpanFinished {
+            self.pop_front();
+        }
+
+        if let Some(retention) = self.retention {
+            while self.events.len() > 1
+                && self.events.front().is_some_and(|oldest| {
+                    oldest
+                        .at()
+                        .checked_add(retention)
+                        .is_some_and(|end| end < at)
+                })
+            {
+                self.pop_front();
+            }
+        }
+    }
+
+    /// Drops the oldest event, along with everything derived from it.
+    fn pop_front(&mut self) {
+        if let Some(event) = self.events.front() {
+            self.bytes = self.bytes.saturating_sub(footprint(event));
+        }
+
+        match self.events.pop_front() {
+            Some(beacon::Event::SpanFinished {
                 span: span::Span::Update { .. },
                 at,
                 ..
-            }) = self.events.pop_front()
-            {
-                self.updates.pop_front();
-
-                if self
-                    .update_rate
-                    .front()
-                    .is_some_and(|bucket| bucket.at < at)
-                {
-                    self.update_rate.pop_front();
+            }) => {
+                if let Some(update) = self.updates.pop_front() {
+                    self.messages.release(update.message);
                 }
+
+                evict(&mut self.update_rate, at);
             }
+            Some(beacon::Event::SpanFinished {
+                span: span::Span::Present { .. },
+                at,
+                ..
+            }) => {
+                evict(&mut self.frame_rate, at);
+            }
+            Some(_) => {}
+            None => return,
+        }
+
+        self.removed += 1;
+        self.stages.evict(Index(self.removed));
+
+        if self
+            .runs
+            .get(1)
+            .is_some_and(|run| run.index <= Index(self.removed))
+        {
+            self.runs.pop_front();
+        }
 
-            self.removed += 1;
+        if self
+            .milestones
+            .front()
+            .is_some_and(|milestone| milestone.index() <= Index(self.removed))
+        {
+            self.milestones.pop_front();
         }
     }
 
+    /// Removes every event, along with everything derived from them.
+    ///
+    /// Indices start over from zero afterwards.
     pub fn clear(&mut self) {
-        self.events.clear();
+        *self = Self {
+            retention: self.retention,
+            ..Self::default()
+        };
     }
 
     pub fn get(&self, playhead: impl Into<Playhead>) -> Option<&beacon::Event> {
@@ -134,6 +250,53 @@ impl Timeline {
         self.events.range(0..index.0).rev()
     }
 
+    pub fn events(&self, start: Index) -> impl Iterator<Item = &beacon::Event> + '_ {
+        self.events
+            .range(start.0.saturating_sub(self.removed).min(self.events.len())..)
+    }
+
+    /// Returns copies of the events since the given index, oldest first,
+    /// with the messages of their updates put back.
+    pub fn restore(&self, start: Index) -> impl Iterator<Item = beacon::Event> + '_ {
+        let start = start.max(Index(self.removed));
+
+        self.events(start).enumerate().map(move |(i, event)| {
+            let mut event = event.clone();
+
+            if let beacon::Event::SpanFinished {
+                span: span::Span::Update { message, .. },
+                ..
+            } = &mut event
+                && let Some(update) = self.update(start + i + 1)
+            {
+                *message = update.message.to_string();
+            }
+
+            event
+        })
+    }
+
+    /// Returns the message of the update at the given index, if it is one.
+    pub fn message(&self, index: Index) -> Option<&str> {
+        self.update(index).map(|update| &*update.message)
+    }
+
+    /// Returns the events that happened within the given duration before
+    /// the playhead, newest first.
+    pub fn recent(
+        &self,
+        playhead: impl Into<Playhead>,
+        duration: Duration,
+    ) -> impl Iterator<Item = (Index, &beacon::Event)> + '_ {
+        let playhead = playhead.into();
+        let end = self.time_at(playhead);
+
+        self.seek_with_index(playhead)
+            .take_while(move |(_, event)| {
+                end.is_none_or(|end| end.duration_since(event.at()).unwrap_or_default() <= duration)
+            })
+    }
+
     pub fn seek_with_index(
         &self,
         playhead: impl Into<Playhead>,
@@ -149,23 +312,57 @@ impl Timeline {
             .map(move |(i, event)| (index - i, event))
     }
 
+    /// Returns the spans of the given [`Stage`] before the playhead, along
+    /// with their index, newest first.
+    ///
+    /// Only the events of the stage are visited.
+    pub fn spans(
+        &self,
+        playhead: Playhead,
+        stage: Stage,
+    ) -> impl DoubleEndedIterator<Item = (Index, &beacon::Event)> + Clone + '_ {
+        let indices = self.stages.get(&stage);
+        let index = self.index(playhead);
+
+        let end = match indices.binary_search(&index) {
+            Ok(i) => i + 1,
+            Err(i) => i,
+        };
+
+        indices.range(0..end).rev().filter_map(move |index| {
+            let event = self.events.get(index.0 - self.removed - 1)?;
+
+            Some((*index, event))
+        })
+    }
+
+    /// Returns the timeframes of the spans of the given [`Stage`] before the
+    /// playhead, newest first.
+    ///
+    /// Only the events of the stage are visited, and `to_duration` can
+    /// still discard any of them.
     pub fn timeframes<'a>(
         &'a self,
         playhead: Playhead,
+        stage: Stage,
         to_duration: impl Fn(&beacon::Event) -> Option<Duration> + Clone + 'a,
     ) -> impl DoubleEndedIterator<Item = Timeframe> + Clone + 'a {
-        self.seek_with_index(playhead)
+        self.spans(playhead, stage)
             .filter_map(move |(index, event)| {
                 let duration = to_duration(event)?;
 
-                Some(Timeframe { index, duration })
+                Some(Timeframe {
+                    index,
+                    at: event.at(),
+                    duration,
+                })
             })
     }
 
     pub fn updates(
         &self,
         playhead: impl Into<Playhead>,
-    ) -> impl DoubleEndedIterator<Item = Update> + Clone + '_ {
+    ) -> impl DoubleEndedIterator<Item = &Update> + Clone + '_ {
         let index = self.index(playhead);
 
         let start = match self
@@ -176,7 +373,24 @@ impl Timeline {
             Err(i) => i,
         };
 
-        self.updates.range(0..start).cloned().rev()
+        self.updates.range(0..start).rev()
+    }
+
+    /// Returns the first update after the given index, if any.
+    pub fn next_update(&self, index: Index) -> Option<&Update> {
+        let i = self.updates.partition_point(|update| update.index <= index);
+
+        self.updates.get(i)
+    }
+
+    /// Returns the update of the event at the given index, if it is one.
+    pub fn update(&self, index: Index) -> Option<&Update> {
+        let i = self
+            .updates
+            .binary_search_by(|update| update.index.cmp(&index))
+            .ok()?;
+
+        self.updates.get(i)
     }
 
     pub fn update_rate(
@@ -196,9 +410,228 @@ impl Timeline {
         self.update_rate.range(0..start).cloned().rev()
     }
 
+    /// Counts the presented frames of every second before the playhead,
+    /// newest first.
+    pub fn frame_rate(
+        &self,
+        playhead: impl Into<Playhead>,
+    ) -> impl DoubleEndedIterator<Item = Bucket> + Clone + '_ {
+        let index = self.index(playhead);
+
+        let start = match self
+            .frame_rate
+            .binary_search_by(|frame| frame.index.cmp(&index))
+        {
+            Ok(i) => i + 1,
+            Err(i) => i,
+        };
+
+        self.frame_rate.range(0..start).cloned().rev()
+    }
+
+    /// Groups the spans of every frame before the playhead, newest first.
+    ///
+    /// A frame is made of the spans since the [`span::Span::Present`] of the
+    /// previous frame up to (and including) its own.
+    pub fn frames(
+        &self,
+        playhead: impl Into<Playhead>,
+    ) -> impl Iterator<Item = Frame> + Clone + '_ {
+        let mut events = self.seek_with_index(playhead).peekable();
+
+        iter::from_fn(move || {
+            let (index, present) = events.find_map(|(index, event)| match event {
+                beacon::Event::SpanFinished {
+                    span: span::Span::Present { .. },
+                    duration,
+                    ..
+                } => Some((index, *duration)),
+                _ => None,
+            })?;
+
+            let mut frame = Frame {
+                index,
+                present,
+                ..Frame::default()
+            };
+
+            while let Some((_, event)) = events.peek() {
+                let beacon::Event::SpanFinished { span, duration, .. } = event else {
+                    let _ = events.next();
+                    continue;
+                };
+
+                let stage = match span {
+                    span::Span::Present { .. } => break,
+                    span::Span::Update { .. } => &mut frame.update,
+                    span::Span::View { .. } => &mut frame.view,
+                    span::Span::Layout { .. } => &mut frame.layout,
+                    span::Span::Interact { .. } => &mut frame.interact,
+                    span::Span::Draw { .. } => &mut frame.draw,
+                    span::Span::Boot | span::Span::Custom { .. } => {
+                        let _ = events.next();
+                        continue;
+                    }
+                };
+
+                *stage += *duration;
+                let _ = events.next();
+            }
+
+            Some(frame)
+        })
+    }
+
+    /// Returns every window that has reported spans, in order of
+    /// appearance.
+    pub fn windows(&self) -> &[window::Id] {
+        &self.windows
+    }
+
+    /// Returns the names of the custom stages with events in the timeline,
+    /// in no particular order.
+    pub fn custom_stages(&self) -> impl Iterator<Item = &str> + '_ {
+        self.stages
+            .custom
+            .iter()
+            .filter(|(_, indices)| !indices.is_empty())
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Returns every run of the application in the timeline, oldest first.
+    ///
+    /// A new run starts every time the application (re)connects.
+    pub fn runs(&self) -> impl DoubleEndedIterator<Item = &Run> + Clone {
+        self.runs.iter()
+    }
+
+    /// Returns the notable events of the timeline, oldest first.
+    pub fn milestones(&self) -> impl DoubleEndedIterator<Item = &Milestone> + Clone {
+        self.milestones.iter()
+    }
+
+    /// Returns the periods of time the application was disconnected for,
+    /// oldest first.
+    pub fn gaps(&self) -> impl Iterator<Item = Gap> + '_ {
+        self.milestones.iter().filter_map(|milestone| {
+            let Milestone::Disconnected(index) = *milestone else {
+                return None;
+            };
+
+            Some(Gap {
+                index,
+                from: self.time_at(Playhead::Paused(index))?,
+                to: self
+                    .runs
+                    .iter()
+                    .find(|run| run.index > index)
+                    .map(|run| run.at),
+            })
+        })
+    }
+
+    /// Returns the run the playhead is in, if any.
+    pub fn run_at(&self, playhead: impl Into<Playhead>) -> Option<&Run> {
+        let index = self.index(playhead);
+
+        self.runs.iter().rev().find(|run| run.index <= index)
+    }
+
+    /// Returns the playhead at the last event of the given run.
+    pub fn end_of(&self, run: &Run) -> Playhead {
+        self.runs
+            .iter()
+            .find(|next| next.index > run.index)
+            .map_or(Playhead::Live, |next| Playhead::Paused(next.index - 1))
+    }
+
     pub fn time_at(&self, playhead: Playhead) -> Option<SystemTime> {
         self.seek(playhead).next().map(beacon::Event::at)
     }
+
+    pub fn start(&self) -> Option<SystemTime> {
+        self.events.front().map(beacon::Event::at)
+    }
+
+    /// Splits the timeline into (at most) `bins` ranges of the same amount
+    /// of events, returning the events per second of each range, oldest
+    /// first.
+    pub fn density(&self, bins: usize) -> Vec<f64> {
+        let bins = bins.min(self.events.len().saturating_sub(1));
+
+        let boundary = |bin: usize| bin * (self.events.len() - 1) / bins;
+
+        (0..bins)
+            .map(|bin| {
+                let start = boundary(bin);
+                let end = boundary(bin + 1);
+
+                let elapsed = self.events[end]
+                    .at()
+                    .duration_since(self.events[start].at())
+                    .unwrap_or_default()
+                    .max(Duration::from_millis(1));
+
+                (end - start) as f64 / elapsed.as_secs_f64()
+            })
+            .collect()
+    }
+
+    pub fn index_at(&self, time: SystemTime) -> Index {
+        Index(self.removed + self.events.partition_point(|event| event.at() <= time))
+    }
+}
+
+/// Estimates the memory used by an event, including the [`Update`] derived
+/// from it.
+///
+/// The interned message of an update is counted by [`Messages`] instead,
+/// since it is usually shared with many others.
+fn footprint(event: &beacon::Event) -> usize {
+    let heap = match event {
+        beacon::Event::Connected { name, .. } => name.len(),
+        beacon::Event::SpanFinished {
+            span: span::Span::Update { .. },
+            ..
+        } => mem::size_of::<Update>(),
+        beacon::Event::SpanFinished {
+            span: span::Span::Custom { name },
+            ..
+        } => name.len(),
+        _ => 0,
+    };
+
+    mem::size_of::<beacon::Event>() + heap
+}
+
+/// Adds an occurrence at the given time to the per-second buckets.
+fn count(buckets: &mut VecDeque<Bucket>, index: Index, at: SystemTime) {
+    let second = at
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    match buckets.back_mut() {
+        Some(bucket) if bucket.second == second => {
+            bucket.at = at;
+            bucket.total += 1;
+        }
+        _ => {
+            buckets.push_back(Bucket {
+                index,
+                at,
+                second,
+                total: 1,
+            });
+        }
+    }
+}
+
+/// Drops the oldest bucket once the occurrence at the given time is gone.
+fn evict(buckets: &mut VecDeque<Bucket>, at: SystemTime) {
+    if buckets.front().is_some_and(|bucket| bucket.at < at) {
+        buckets.pop_front();
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -298,17 +731,30 @@ impl Sub<usize> for Index {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Timeframe {
     pub index: Index,
+    pub at: SystemTime,
     pub duration: Duration,
 }
 
 #[derive(Debug, Clone)]
 pub struct Update {
     pub index: Index,
+    pub at: SystemTime,
     pub duration: Duration,
     pub number: usize,
     pub tasks: usize,
     pub subscriptions: usize,
-    pub message: String,
+    pub message: Arc<str>,
+}
+
+impl Update {
+    /// Returns the variant name of the debug-formatted message; that is,
+    /// everything before its first `(`, `{` or space.
+    pub fn variant(&self) -> &str {
+        self.message
+            .split(['(', '{', ' '])
+            .next()
+            .unwrap_or(&self.message)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -318,3 +764,178 @@ pub struct Bucket {
     pub second: u64,
     pub total: usize,
 }
+
+/// The events since a connection of the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub number: usize,
+    pub index: Index,
+    pub at: SystemTime,
+}
+
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let datetime: chrono::DateTime<chrono::Local> = self.at.into();
+
+        write!(f, "Run {} ({})", self.number, datetime.format("%H:%M:%S"))
+    }
+}
+
+/// A notable event of the timeline, worth pointing out while browsing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Milestone {
+    Disconnected(Index),
+    ThemeChanged(Index),
+}
+
+impl Milestone {
+    pub fn index(self) -> Index {
+        match self {
+            Milestone::Disconnected(index) | Milestone::ThemeChanged(index) => index,
+        }
+    }
+}
+
+/// The distinct update messages of a [`Timeline`], shared by every update
+/// with the same message.
+///
+/// High-frequency messages (like `Tick`) are usually identical, so this
+/// saves a copy of each.
+#[derive(Debug, Clone, Default)]
+struct Messages {
+    interned: HashSet<Arc<str>>,
+    /// The length of every interned message.
+    bytes: usize,
+}
+
+impl Messages {
+    fn intern(&mut self, message: &str) -> Arc<str> {
+        if let Some(interned) = self.interned.get(message) {
+            return interned.clone();
+        }
+
+        let message: Arc<str> = Arc::from(message);
+        let _ = self.interned.insert(message.clone());
+        self.bytes += message.len();
+
+        message
+    }
+
+    /// Forgets the message of an evicted update, unless some other update
+    /// still shares it.
+    fn release(&mut self, message: Arc<str>) {
+        // One reference is ours and the other is the interned one
+        if Arc::strong_count(&message) == 2 && self.interned.remove(&message) {
+            self.bytes -= message.len();
+        }
+    }
+}
+
+/// A kind of span with its own track of events in a [`Timeline`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stage {
+    Update,
+    View,
+    Layout,
+    Interact,
+    Draw,
+    Present,
+    Custom(String),
+}
+
+/// The indices of the events of every [`Stage`], oldest first.
+#[derive(Debug, Clone, Default)]
+struct Stages {
+    builtin: [VecDeque<Index>; 6],
+    custom: HashMap<String, VecDeque<Index>>,
+}
+
+impl Stages {
+    const EMPTY: &VecDeque<Index> = &VecDeque::new();
+
+    fn get(&self, stage: &Stage) -> &VecDeque<Index> {
+        let builtin = match stage {
+            Stage::Update => 0,
+            Stage::View => 1,
+            Stage::Layout => 2,
+            Stage::Interact => 3,
+            Stage::Draw => 4,
+            Stage::Present => 5,
+            Stage::Custom(name) => return self.custom.get(name).unwrap_or(Self::EMPTY),
+        };
+
+        &self.builtin[builtin]
+    }
+
+    fn push(&mut self, span: &span::Span, index: Index) {
+        let builtin = match span {
+            span::Span::Boot => return,
+            span::Span::Update { .. } => 0,
+            span::Span::View { .. } => 1,
+            span::Span::Layout { .. } => 2,
+            span::Span::Interact { .. } => 3,
+            span::Span::Draw { .. } => 4,
+            span::Span::Present { .. } => 5,
+            span::Span::Custom { name } => {
+                match self.custom.get_mut(name) {
+                    Some(indices) => indices.push_back(index),
+                    None => {
+                        let _ = self.custom.insert(name.clone(), VecDeque::from([index]));
+                    }
+                }
+
+                return;
+            }
+        };
+
+        self.builtin[builtin].push_back(index);
+    }
+
+    /// Forgets the given index, now that its event is gone.
+    fn evict(&mut self, index: Index) {
+        for indices in self.builtin.iter_mut().chain(self.custom.values_mut()) {
+            if indices.front() == Some(&index) {
+                let _ = indices.pop_front();
+                return;
+            }
+        }
+    }
+}
+
+/// A period of time the application was disconnected for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    /// The index of the disconnection.
+    pub index: Index,
+    pub from: SystemTime,
+    /// When the application connected again, if it did.
+    pub to: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Frame {
+    pub index: Index,
+    pub update: Duration,
+    pub view: Duration,
+    pub layout: Duration,
+    pub interact: Duration,
+    pub draw: Duration,
+    pub present: Duration,
+}
+
+impl Frame {
+    pub fn stages(&self) -> [Duration; 6] {
+        [
+            self.update,
+            self.view,
+            self.layout,
+            self.interact,
+            self.draw,
+            self.present,
+        ]
+    }
+
+    pub fn total(&self) -> Duration {
+        self.stages().into_iter().sum()
+    }
+}
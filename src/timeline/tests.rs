@@ -0,0 +1,138 @@
+use super::*;
+
+fn update(number: usize, message: &str, at: SystemTime) -> beacon::Event {
+    beacon::Event::SpanFinished {
+        at,
+        duration: Duration::from_millis(1),
+        span: span::Span::Update {
+            number,
+            message: message.to_owned(),
+            tasks: 0,
+            subscriptions: 0,
+        },
+    }
+}
+
+#[test]
+fn the_same_message_is_interned_once() {
+    let mut messages = Messages::default();
+
+    let tick = messages.intern("Tick");
+    let again = messages.intern("Tick");
+    let other = messages.intern("Other");
+
+    assert!(Arc::ptr_eq(&tick, &again));
+    assert!(!Arc::ptr_eq(&tick, &other));
+    assert_eq!(messages.interned.len(), 2);
+}
+
+#[test]
+fn messages_are_released_with_their_last_update() {
+    let mut messages = Messages::default();
+
+    let first = messages.intern("Tick");
+    let second = messages.intern("Tick");
+
+    // The interned copy and `first` still share it
+    messages.release(second);
+    assert!(messages.interned.contains("Tick"));
+
+    messages.release(first);
+    assert!(messages.interned.is_empty());
+    assert_eq!(messages.bytes, 0);
+}
+
+#[test]
+fn interned_messages_are_counted_once() {
+    let mut timeline = Timeline::new();
+    let now = SystemTime::now();
+
+    timeline.push(update(1, "Tick", now));
+    let bytes = timeline.bytes();
+
+    timeline.push(update(2, "Tick", now));
+    let shared = timeline.bytes() - bytes;
+
+    timeline.push(update(3, "A unique message", now));
+    let unique = timeline.bytes() - bytes - shared;
+
+    assert_eq!(unique - shared, "A unique message".len());
+}
+
+#[test]
+fn updates_share_the_interned_message() {
+    let mut timeline = Timeline::new();
+    let now = SystemTime::now();
+
+    timeline.push(update(1, "Tick", now));
+    timeline.push(update(2, "Tick", now));
+
+    let updates: Vec<_> = timeline.updates(Playhead::Live).collect();
+
+    assert!(Arc::ptr_eq(&updates[0].message, &updates[1].message));
+    assert_eq!(timeline.message(Index(1)), Some("Tick"));
+    assert_eq!(timeline.messages.interned.len(), 1);
+
+    let restored: Vec<_> = timeline.restore(Index(0)).collect();
+
+    assert!(matches!(
+        &restored[1],
+        beacon::Event::SpanFinished {
+            span: span::Span::Update { message, .. },
+            ..
+        } if message == "Tick"
+    ));
+}
+
+#[test]
+fn evicted_messages_are_released() {
+    let mut timeline = Timeline::new();
+    let now = SystemTime::now();
+
+    timeline.set_retention(Some(Duration::from_secs(1)));
+    timeline.push(update(1, "Old", now));
+    timeline.push(update(2, "Tick", now));
+    timeline.push(update(3, "Tick", now + Duration::from_secs(2)));
+
+    assert_eq!(timeline.len(), 1);
+    assert!(!timeline.messages.interned.contains("Old"));
+    assert!(timeline.messages.interned.contains("Tick"));
+}
+
+#[test]
+fn huge_retentions_keep_everything() {
+    let mut timeline = Timeline::new();
+    let now = SystemTime::now();
+
+    timeline.set_retention(Some(Duration::MAX));
+    timeline.push(update(1, "Tick", now));
+    timeline.push(update(2, "Tick", now + Duration::from_secs(2)));
+
+    assert_eq!(timeline.len(), 2);
+}
+
+#[test]
+fn clearing_releases_every_message() {
+    let mut timeline = Timeline::new();
+
+    timeline.push(update(1, "Tick", SystemTime::now()));
+    timeline.clear();
+
+    assert!(timeline.messages.interned.is_empty());
+    assert_eq!(timeline.message(Index(1)), None);
+}
+
+#[test]
+fn next_update_skips_the_current_one() {
+    let mut timeline = Timeline::new();
+    let now = SystemTime::now();
+
+    timeline.push(update(1, "Tick", now));
+    timeline.push(update(2, "Tick", now));
+
+    assert_eq!(
+        timeline.next_update(Index(1)).map(|update| update.index),
+        Some(Index(2))
+    );
+    assert!(timeline.next_update(Index(2)).is_none());
+}
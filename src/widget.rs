@@ -1,32 +1,53 @@
 use iced::border;
 use iced::padding;
 use iced::theme;
-use iced::widget::{column, container, space, text, tooltip};
-use iced::{Background, Color, Element, Theme};
+use iced::widget::{column, container, row, space, text, tooltip};
+use iced::{Background, Center, Color, Element, Theme};
 
+pub mod hover;
+pub mod markers;
+pub mod minimap;
+
+pub use hover::hover;
 pub use iced_palace::widget::diffused_text;
+pub use markers::markers;
+pub use minimap::minimap;
 
 pub fn card<'a, Message: 'a>(
     title: impl text::IntoFragment<'a>,
     content: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    card_with_action(title, space(), content)
+}
+
+/// A [`card`] with an extra widget (usually a small button) at the right
+/// side of its title.
+pub fn card_with_action<'a, Message: 'a>(
+    title: impl text::IntoFragment<'a>,
+    action: impl Into<Element<'a, Message>>,
+    content: impl Into<Element<'a, Message>>,
 ) -> Element<'a, Message> {
     container(column![
-        container(diffused_text(title)).padding(padding::all(10).bottom(5)),
+        container(row![diffused_text(title), space::horizontal(), action.into()].align_y(Center))
+            .padding(padding::all(10).bottom(5)),
         content.into()
     ])
-    .style(|theme| {
-        let style = container::bordered_box(theme);
-
-        container::Style {
-            border: border::rounded(border::top(5))
-                .width(1)
-                .color(theme.palette().background.weak.color),
-            ..style
-        }
-    })
+    .style(card_style)
     .into()
 }
 
+/// The style of a [`card`].
+pub fn card_style(theme: &Theme) -> container::Style {
+    let style = container::bordered_box(theme);
+
+    container::Style {
+        border: border::rounded(border::top(5))
+            .width(1)
+            .color(theme.palette().background.weak.color),
+        ..style
+    }
+}
+
 pub fn circle<'a, Message: 'a>(
     color: impl Fn(&theme::Palette) -> Color + 'a,
 ) -> Element<'a, Message> {
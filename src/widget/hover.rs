@@ -0,0 +1,81 @@
+use crate::timeline;
+
+use iced::mouse;
+use iced::widget::canvas;
+use iced::{Element, Fill, Rectangle, Renderer, Theme};
+
+use std::ops::RangeInclusive;
+
+/// An invisible layer meant to be stacked on top of a timeline slider
+/// spanning the same `range`, reporting the index under the cursor.
+///
+/// It never captures any events, so the slider below keeps working.
+pub fn hover<'a, Message: Clone + 'a>(
+    range: RangeInclusive<timeline::Index>,
+    on_hover: fn(Option<timeline::Index>) -> Message,
+) -> Element<'a, Message> {
+    canvas(Hover { range, on_hover })
+        .width(Fill)
+        .height(Fill)
+        .into()
+}
+
+/// Half the width of the slider handle; its center never gets closer than
+/// this to the edges of the track.
+pub const INSET: f32 = 7.0;
+
+/// Returns the index of the slider track at the given horizontal position.
+pub fn index_at(range: &RangeInclusive<timeline::Index>, x: f32, width: f32) -> timeline::Index {
+    let start = *range.start();
+    let events = (f64::from(*range.end()) - f64::from(start)).max(0.0);
+
+    let track = (width - 2.0 * INSET).max(1.0);
+    let ratio = ((x - INSET) / track).clamp(0.0, 1.0);
+
+    start + (events * f64::from(ratio)).round() as usize
+}
+
+struct Hover<Message> {
+    range: RangeInclusive<timeline::Index>,
+    on_hover: fn(Option<timeline::Index>) -> Message,
+}
+
+impl<Message: Clone> canvas::Program<Message> for Hover<Message> {
+    type State = Option<timeline::Index>;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let iced::Event::Mouse(mouse::Event::CursorMoved { .. } | mouse::Event::CursorLeft) = event
+        else {
+            return None;
+        };
+
+        let hovered = cursor
+            .position_in(bounds)
+            .map(|position| index_at(&self.range, position.x, bounds.width));
+
+        if hovered == *state {
+            return None;
+        }
+
+        *state = hovered;
+
+        Some(canvas::Action::publish((self.on_hover)(hovered)))
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        _renderer: &Renderer,
+        _theme: &Theme,
+        _bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,144 @@
+use crate::timeline;
+use crate::widget::hover;
+
+use iced::mouse;
+use iced::widget::canvas;
+use iced::{Color, Element, Fill, Point, Rectangle, Renderer, Size, Theme, theme};
+
+use std::ops::RangeInclusive;
+
+/// A notable index of the timeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marker {
+    pub index: timeline::Index,
+    pub kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Bookmark,
+    Disconnection,
+    ThemeChange,
+}
+
+impl Kind {
+    fn color(self, palette: &theme::Palette) -> Color {
+        match self {
+            Kind::Bookmark => palette.warning.base.color,
+            Kind::Disconnection => palette.danger.base.color,
+            Kind::ThemeChange => palette.primary.base.color,
+        }
+    }
+}
+
+/// A thin strip of ticks meant to be placed right below a timeline slider
+/// spanning the same `range`.
+///
+/// Clicking a tick produces the message of its index.
+pub fn markers<'a, Message: Clone + 'a>(
+    range: RangeInclusive<timeline::Index>,
+    markers: Vec<Marker>,
+    on_select: fn(timeline::Index) -> Message,
+) -> Element<'a, Message> {
+    canvas(Markers {
+        range,
+        markers,
+        on_select,
+    })
+    .width(Fill)
+    .height(Markers::<Message>::HEIGHT)
+    .into()
+}
+
+struct Markers<Message> {
+    range: RangeInclusive<timeline::Index>,
+    markers: Vec<Marker>,
+    on_select: fn(timeline::Index) -> Message,
+}
+
+impl<Message> Markers<Message> {
+    const HEIGHT: f32 = 6.0;
+
+    fn x(&self, index: timeline::Index, width: f32) -> f32 {
+        let start = f64::from(*self.range.start());
+        let end = f64::from(*self.range.end());
+
+        let ratio = if end > start {
+            ((f64::from(index) - start) / (end - start)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        hover::INSET + (width - 2.0 * hover::INSET).max(0.0) * ratio as f32
+    }
+
+    fn marker_at(&self, x: f32, width: f32) -> Option<&Marker> {
+        const TOLERANCE: f32 = 3.0;
+
+        self.markers
+            .iter()
+            .map(|marker| (marker, (self.x(marker.index, width) - x).abs()))
+            .filter(|(_, distance)| *distance <= TOLERANCE)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(marker, _)| marker)
+    }
+}
+
+impl<Message: Clone> canvas::Program<Message> for Markers<Message> {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut (),
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event else {
+            return None;
+        };
+
+        let position = cursor.position_in(bounds)?;
+        let marker = self.marker_at(position.x, bounds.width)?;
+
+        Some(canvas::Action::publish((self.on_select)(marker.index)).and_capture())
+    }
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let palette = theme.palette();
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+        for marker in &self.markers {
+            let x = self.x(marker.index, bounds.width);
+
+            frame.fill_rectangle(
+                Point::new(x - 1.0, 0.0),
+                Size::new(2.0, bounds.height),
+                marker.kind.color(palette),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &(),
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        match cursor.position_in(bounds) {
+            Some(position) if self.marker_at(position.x, bounds.width).is_some() => {
+                mouse::Interaction::Pointer
+            }
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
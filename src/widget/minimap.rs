@@ -0,0 +1,110 @@
+use crate::timeline;
+use crate::widget::hover;
+
+use iced::mouse;
+use iced::widget::canvas;
+use iced::{Element, Fill, Point, Rectangle, Renderer, Size, Theme};
+
+use std::ops::RangeInclusive;
+
+/// A thin heat strip of the event density of a timeline, meant to be placed
+/// right above a timeline slider spanning the same `range`.
+///
+/// Clicking the strip produces the message of the index under the cursor.
+pub fn minimap<'a, Message: Clone + 'a>(
+    range: RangeInclusive<timeline::Index>,
+    density: Vec<f64>,
+    on_select: fn(timeline::Index) -> Message,
+) -> Element<'a, Message> {
+    canvas(Minimap {
+        range,
+        density,
+        on_select,
+    })
+    .width(Fill)
+    .height(Minimap::<Message>::HEIGHT)
+    .into()
+}
+
+struct Minimap<Message> {
+    range: RangeInclusive<timeline::Index>,
+    density: Vec<f64>,
+    on_select: fn(timeline::Index) -> Message,
+}
+
+impl<Message> Minimap<Message> {
+    const HEIGHT: f32 = 8.0;
+}
+
+impl<Message: Clone> canvas::Program<Message> for Minimap<Message> {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut (),
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event else {
+            return None;
+        };
+
+        let position = cursor.position_in(bounds)?;
+
+        Some(
+            canvas::Action::publish((self.on_select)(hover::index_at(
+                &self.range,
+                position.x,
+                bounds.width,
+            )))
+            .and_capture(),
+        )
+    }
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let palette = theme.palette();
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+        let max = self.density.iter().copied().fold(0.0, f64::max);
+
+        if max > 0.0 {
+            let track = (bounds.width - 2.0 * hover::INSET).max(0.0);
+            let width = track / self.density.len() as f32;
+
+            for (i, density) in self.density.iter().enumerate() {
+                // Logarithmic, so quiet regions are still visible next to
+                // bursts
+                let heat = (density.ln_1p() / max.ln_1p()) as f32;
+
+                frame.fill_rectangle(
+                    Point::new(hover::INSET + width * i as f32, 0.0),
+                    Size::new(width.ceil(), bounds.height),
+                    palette.primary.base.color.scale_alpha(heat),
+                );
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &(),
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}